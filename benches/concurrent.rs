@@ -0,0 +1,96 @@
+//! Throughput of the concurrent ring buffers with one writing and one reading thread
+//!
+//! Run with `cargo bench`, and with `--features cache-line-128` to compare cursor padding
+use std::hint::black_box;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use sringbuf::RingBuffer;
+use sringbuf::{mpmc, mpsc};
+
+const COUNT: u64 = 10_000_000;
+
+fn report(name: &str, elapsed: Duration) {
+    let rate = COUNT as f64 / elapsed.as_secs_f64() / 1e6;
+    println!("{name:>6}: {elapsed:>10.2?} ({rate:.1} M elements/s)");
+}
+
+fn spsc() -> Duration {
+    let ring_buffer: RingBuffer<u64, 1024> = RingBuffer::new();
+    let (mut producer, mut consumer) = ring_buffer.split();
+
+    let start = Instant::now();
+    let writer = thread::spawn(move || {
+        for i in 0..COUNT {
+            while producer.try_write(i).is_err() {
+                std::hint::spin_loop();
+            }
+        }
+    });
+
+    let mut received = 0;
+    while received < COUNT {
+        if let Some(data) = consumer.read() {
+            black_box(data);
+            received += 1;
+        }
+    }
+
+    writer.join().unwrap();
+    start.elapsed()
+}
+
+fn mpmc() -> Duration {
+    let queue: mpmc::Queue<u64, 1024> = mpmc::Queue::new();
+
+    let start = Instant::now();
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            for i in 0..COUNT {
+                while queue.try_write(i).is_err() {
+                    std::hint::spin_loop();
+                }
+            }
+        });
+
+        let mut received = 0;
+        while received < COUNT {
+            if let Some(data) = queue.read() {
+                black_box(data);
+                received += 1;
+            }
+        }
+    });
+    start.elapsed()
+}
+
+fn mpsc() -> Duration {
+    let mut queue: mpsc::Queue<u64, 1024> = mpsc::Queue::new();
+    let (producer, mut consumer) = queue.split();
+
+    let start = Instant::now();
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            for i in 0..COUNT {
+                while producer.try_write(i).is_err() {
+                    std::hint::spin_loop();
+                }
+            }
+        });
+
+        let mut received = 0;
+        while received < COUNT {
+            if let Some(data) = consumer.read() {
+                black_box(data);
+                received += 1;
+            }
+        }
+    });
+    start.elapsed()
+}
+
+fn main() {
+    report("spsc", spsc());
+    report("mpmc", mpmc());
+    report("mpsc", mpsc());
+}
@@ -0,0 +1,262 @@
+//! A ring buffer maintaining the sum, minimum, maximum and mean of its window
+use core::ops::{Add, Deref, Sub};
+
+use crate::RingBuffer;
+
+/// A FIFO ring buffer of the last `N` values written, keeping their sum, minimum and maximum up
+/// to date as values enter and leave the window
+///
+/// Each write updates the aggregates in amortized constant time: the sum is adjusted by the
+/// value written and the one overwritten, and the minimum and maximum come from monotonic
+/// queues of the candidates still in the window. The sum is kept in a wider accumulator `A`,
+/// an `f64` by default, so it cannot overflow `T`; an `f64` sums integers exactly up to
+/// 2<sup>53</sup>, while floating-point sums drift slightly as rounding errors accumulate. Other
+/// element types, such as `i64`, need an accumulator like `i128`
+#[derive(Clone, Debug)]
+pub struct AggregatingRingBuffer<T, const N: usize, A = f64> {
+    ring_buffer: RingBuffer<T, N>,
+    sum: A,
+    min: RingBuffer<(u64, T), N>,
+    max: RingBuffer<(u64, T), N>,
+    next: u64,
+}
+
+impl<T, const N: usize, A> AggregatingRingBuffer<T, N, A>
+where
+    T: Copy + PartialOrd,
+    A: Copy + Default + From<T> + Add<Output = A> + Sub<Output = A>,
+{
+    /// Creates a new, empty AggregatingRingBuffer instance
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let window: sringbuf::AggregatingRingBuffer<u32, 4> = sringbuf::AggregatingRingBuffer::new();
+    /// assert_eq!(window.sum(), 0.0);
+    /// assert_eq!(window.min(), None);
+    /// ```
+    pub fn new() -> AggregatingRingBuffer<T, N, A> {
+        AggregatingRingBuffer {
+            ring_buffer: RingBuffer::new(),
+            sum: A::default(),
+            min: RingBuffer::new(),
+            max: RingBuffer::new(),
+            next: 0,
+        }
+    }
+
+    /// Writes a value to the window, overwriting and returning the oldest one if it is full
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut window: sringbuf::AggregatingRingBuffer<i32, 3> = sringbuf::AggregatingRingBuffer::new();
+    /// window.extend([4, -2, 7, 1]);
+    /// assert_eq!(window.sum(), 6.0);
+    /// assert_eq!(window.min(), Some(-2));
+    /// assert_eq!(window.max(), Some(7));
+    /// ```
+    pub fn write(&mut self, data: T) -> Option<T> {
+        let evicted = self.ring_buffer.write(data);
+        if let Some(evicted) = evicted {
+            self.sum = self.sum - A::from(evicted);
+        }
+        self.sum = self.sum + A::from(data);
+
+        let sequence = self.next;
+        self.next += 1;
+        let oldest = self.oldest_sequence();
+        push_monotonic(&mut self.min, oldest, sequence, data, |back| back >= data);
+        push_monotonic(&mut self.max, oldest, sequence, data, |back| back <= data);
+        evicted
+    }
+
+    /// Reads the oldest value, removing it from the window
+    pub fn read(&mut self) -> Option<T> {
+        let oldest = self.oldest_sequence();
+        let data = self.ring_buffer.read()?;
+        self.sum = self.sum - A::from(data);
+
+        for candidates in [&mut self.min, &mut self.max] {
+            if candidates
+                .peek()
+                .is_some_and(|&(sequence, _)| sequence == oldest)
+            {
+                candidates.read();
+            }
+        }
+        Some(data)
+    }
+
+    /// Returns the sum of the values in the window, or the default value of `A` if it is empty
+    pub fn sum(&self) -> A {
+        self.sum
+    }
+
+    /// Returns the smallest value in the window
+    pub fn min(&self) -> Option<T> {
+        self.min.peek().map(|&(_, data)| data)
+    }
+
+    /// Returns the largest value in the window
+    pub fn max(&self) -> Option<T> {
+        self.max.peek().map(|&(_, data)| data)
+    }
+
+    /// Returns the arithmetic mean of the values in the window
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut window: sringbuf::AggregatingRingBuffer<u16, 4> = sringbuf::AggregatingRingBuffer::new();
+    /// assert_eq!(window.mean(), None);
+    /// window.extend([1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(window.mean(), Some(4.5));
+    /// ```
+    pub fn mean(&self) -> Option<f64>
+    where
+        A: Into<f64>,
+    {
+        if self.ring_buffer.is_empty() {
+            return None;
+        }
+
+        Some(self.sum.into() / self.ring_buffer.len() as f64)
+    }
+
+    /// Drops every value of the window and resets the aggregates
+    pub fn clear(&mut self) {
+        self.ring_buffer.clear();
+        self.min.clear();
+        self.max.clear();
+        self.sum = A::default();
+    }
+
+    /// Returns the underlying ring buffer, dropping the aggregates
+    pub fn into_inner(self) -> RingBuffer<T, N> {
+        self.ring_buffer
+    }
+
+    /// Returns the sequence number of the oldest value in the window
+    fn oldest_sequence(&self) -> u64 {
+        self.next - self.ring_buffer.len() as u64
+    }
+}
+
+/// Appends a candidate to a monotonic queue, first dropping the candidates that left the window
+/// and those that `dominated` says can no longer be the extreme
+fn push_monotonic<T: Copy, const N: usize>(
+    candidates: &mut RingBuffer<(u64, T), N>,
+    oldest: u64,
+    sequence: u64,
+    data: T,
+    dominated: impl Fn(T) -> bool,
+) {
+    let expired = candidates
+        .position(|&(sequence, _)| sequence >= oldest)
+        .unwrap_or(candidates.len());
+    candidates.advance_read(expired);
+    while candidates.last().is_some_and(|&(_, back)| dominated(back)) {
+        candidates.pop_back();
+    }
+    candidates.write((sequence, data));
+}
+
+impl<T, const N: usize, A> Deref for AggregatingRingBuffer<T, N, A> {
+    type Target = RingBuffer<T, N>;
+
+    fn deref(&self) -> &RingBuffer<T, N> {
+        &self.ring_buffer
+    }
+}
+
+impl<T, const N: usize, A> Default for AggregatingRingBuffer<T, N, A>
+where
+    T: Copy + PartialOrd,
+    A: Copy + Default + From<T> + Add<Output = A> + Sub<Output = A>,
+{
+    /// Creates an empty window
+    fn default() -> AggregatingRingBuffer<T, N, A> {
+        AggregatingRingBuffer::new()
+    }
+}
+
+impl<T, const N: usize, A> Extend<T> for AggregatingRingBuffer<T, N, A>
+where
+    T: Copy + PartialOrd,
+    A: Copy + Default + From<T> + Add<Output = A> + Sub<Output = A>,
+{
+    /// Writes every element of an iterator to the window
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for data in iter {
+            self.write(data);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::AggregatingRingBuffer;
+
+    #[test]
+    fn write_matches_recomputed() {
+        let mut window: AggregatingRingBuffer<i64, 5, i128> = AggregatingRingBuffer::new();
+        let mut state = 1u64;
+
+        for _ in 0..1000 {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            window.write((state >> 40) as i64 - (1 << 23));
+
+            assert_eq!(
+                window.sum(),
+                window.iter().map(|&data| i128::from(data)).sum()
+            );
+            assert_eq!(window.min(), window.iter().copied().min());
+            assert_eq!(window.max(), window.iter().copied().max());
+        }
+    }
+
+    #[test]
+    fn read_shrinks_window() {
+        let mut window: AggregatingRingBuffer<u32, 4> = AggregatingRingBuffer::new();
+
+        window.extend([3, 1, 4, 1]);
+        assert_eq!(window.read(), Some(3));
+        assert_eq!(
+            (window.sum(), window.min(), window.max()),
+            (6.0, Some(1), Some(4))
+        );
+        assert_eq!(window.read(), Some(1));
+        assert_eq!(window.read(), Some(4));
+        assert_eq!(
+            (window.sum(), window.min(), window.max()),
+            (1.0, Some(1), Some(1))
+        );
+        assert_eq!(window.read(), Some(1));
+        assert_eq!(
+            (window.sum(), window.min(), window.max()),
+            (0.0, None, None)
+        );
+        assert_eq!(window.read(), None);
+
+        window.extend([5, 2]);
+        window.clear();
+        assert_eq!(window.mean(), None);
+        assert_eq!(window.max(), None);
+    }
+
+    #[test]
+    fn sum_wider_than_element() {
+        let mut window: AggregatingRingBuffer<u8, 4> = AggregatingRingBuffer::new();
+        window.extend([100; 4]);
+        assert_eq!(window.sum(), 400.0);
+        assert_eq!(window.mean(), Some(100.0));
+
+        let mut window: AggregatingRingBuffer<u8, 4, u16> = AggregatingRingBuffer::new();
+        window.extend([u8::MAX; 6]);
+        assert_eq!(window.sum(), 4 * u16::from(u8::MAX));
+        assert_eq!(window.max(), Some(u8::MAX));
+    }
+}
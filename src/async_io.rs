@@ -0,0 +1,176 @@
+//! tokio [`AsyncRead`] and [`AsyncWrite`] implementations for byte ring buffers
+use core::pin::Pin;
+use core::task::{Context, Poll, ready};
+use std::io;
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+use crate::spsc::{Consumer, Producer};
+use crate::{Cursor, GenericRingBuffer, Storage};
+
+impl<S, P, C> AsyncRead for GenericRingBuffer<u8, S, P, C>
+where
+    S: Storage<u8> + Unpin,
+    C: Cursor,
+    P: Unpin,
+{
+    /// Reads the oldest bytes that fit in `buf`; an empty ring buffer reads as the end of the
+    /// data, since nothing else can write to it while it is borrowed
+    fn poll_read(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let count = self.get_mut().read_into(buf.initialize_unfilled());
+        buf.advance(count);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<S, P, C> AsyncWrite for GenericRingBuffer<u8, S, P, C>
+where
+    S: Storage<u8> + Unpin,
+    C: Cursor,
+    P: Unpin,
+{
+    /// Writes as many bytes as fit in the free space; a full ring buffer writes zero bytes
+    /// rather than overwriting unread data
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Poll::Ready(Ok(self.get_mut().write_from(buf)))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<const N: usize> AsyncRead for Consumer<'_, u8, N> {
+    /// Waits for bytes to arrive, reading the end of the data once the producer is closed
+    /// and everything queued has been read
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let consumer = self.get_mut();
+        if buf.remaining() == 0 {
+            return Poll::Ready(Ok(()));
+        }
+
+        ready!(consumer.poll_read_ready(cx));
+        let count = consumer.read_into(buf.initialize_unfilled());
+        buf.advance(count);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<const N: usize> AsyncWrite for Producer<'_, u8, N> {
+    /// Waits for free space and writes as many bytes as fit, failing with
+    /// [`io::ErrorKind::BrokenPipe`] once the ring buffer is closed
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let producer = self.get_mut();
+        if producer.is_closed() {
+            return Poll::Ready(Err(io::ErrorKind::BrokenPipe.into()));
+        }
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        ready!(producer.poll_ready(cx));
+        Poll::Ready(Ok(producer.write_from(buf)))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    /// Closes the ring buffer, so the consumer reads the end of the data once it is drained
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.get_mut().close();
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RingBuffer;
+
+    use std::io;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[tokio::test]
+    async fn ring_buffer_round_trip() {
+        let mut ring_buffer: RingBuffer<u8, 4> = RingBuffer::new();
+
+        assert_eq!(
+            AsyncWriteExt::write(&mut ring_buffer, b"hello")
+                .await
+                .unwrap(),
+            4
+        );
+        let mut dst = [0; 8];
+        assert_eq!(
+            AsyncReadExt::read(&mut ring_buffer, &mut dst)
+                .await
+                .unwrap(),
+            4
+        );
+        assert_eq!(&dst[..4], b"hell");
+        assert_eq!(
+            AsyncReadExt::read(&mut ring_buffer, &mut dst)
+                .await
+                .unwrap(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn ring_buffer_write_all_full() {
+        let mut ring_buffer: RingBuffer<u8, 2> = RingBuffer::new();
+
+        let error = ring_buffer.write_all(b"abc").await.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::WriteZero);
+    }
+
+    #[tokio::test]
+    async fn split_pipe_copies_everything() {
+        let data: Vec<u8> = (0..=255).cycle().take(10_000).collect();
+        let ring_buffer: RingBuffer<u8, 64> = RingBuffer::new();
+        let (mut producer, mut consumer) = ring_buffer.split();
+
+        let writer = async {
+            producer.write_all(&data).await.unwrap();
+            producer.shutdown().await.unwrap();
+        };
+        let reader = async {
+            let mut received = Vec::new();
+            consumer.read_to_end(&mut received).await.unwrap();
+            received
+        };
+
+        let ((), received) = tokio::join!(writer, reader);
+        assert_eq!(received, data);
+    }
+
+    #[tokio::test]
+    async fn split_write_after_consumer_drop() {
+        let ring_buffer: RingBuffer<u8, 4> = RingBuffer::new();
+        let (mut producer, consumer) = ring_buffer.split();
+
+        drop(consumer);
+        let error = producer.write(b"a").await.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::BrokenPipe);
+    }
+}
@@ -0,0 +1,120 @@
+//! A moving average over the last samples pushed
+use core::ops::Deref;
+
+use crate::RingBuffer;
+
+/// The average of the last `N` samples pushed, as used to smooth noisy sensor readings
+///
+/// The running sum is kept as an `f64`, so integer samples never overflow it and are summed
+/// exactly. Until `N` samples have been pushed, the average covers only the samples seen so far
+#[derive(Clone, Debug)]
+pub struct MovingAverage<T, const N: usize> {
+    ring_buffer: RingBuffer<T, N>,
+    sum: f64,
+}
+
+impl<T: Copy + Into<f64>, const N: usize> MovingAverage<T, N> {
+    /// Creates a new MovingAverage instance with no samples
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let filter: sringbuf::MovingAverage<u16, 8> = sringbuf::MovingAverage::new();
+    /// assert_eq!(filter.average(), None);
+    /// ```
+    pub const fn new() -> MovingAverage<T, N> {
+        MovingAverage {
+            ring_buffer: RingBuffer::new(),
+            sum: 0.0,
+        }
+    }
+
+    /// Adds a sample, returning the oldest one if it fell out of the window
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut filter: sringbuf::MovingAverage<u8, 4> = sringbuf::MovingAverage::new();
+    /// filter.push(10);
+    /// filter.push(20);
+    /// assert_eq!(filter.average(), Some(15.0));
+    ///
+    /// filter.extend([30, 40, 50]);
+    /// assert_eq!(filter.average(), Some(35.0));
+    /// ```
+    pub fn push(&mut self, sample: T) -> Option<T> {
+        let evicted = self.ring_buffer.write(sample);
+        if let Some(evicted) = evicted {
+            self.sum -= evicted.into();
+        }
+        self.sum += sample.into();
+        evicted
+    }
+
+    /// Returns the average of the samples in the window, or `None` if there are none
+    pub fn average(&self) -> Option<f64> {
+        if self.ring_buffer.is_empty() {
+            return None;
+        }
+
+        Some(self.sum / self.ring_buffer.len() as f64)
+    }
+
+    /// Returns the sum of the samples in the window
+    pub fn sum(&self) -> f64 {
+        self.sum
+    }
+
+    /// Drops every sample
+    pub fn clear(&mut self) {
+        self.ring_buffer.clear();
+        self.sum = 0.0;
+    }
+}
+
+impl<T, const N: usize> Deref for MovingAverage<T, N> {
+    type Target = RingBuffer<T, N>;
+
+    fn deref(&self) -> &RingBuffer<T, N> {
+        &self.ring_buffer
+    }
+}
+
+impl<T: Copy + Into<f64>, const N: usize> Default for MovingAverage<T, N> {
+    /// Creates a moving average with no samples
+    fn default() -> MovingAverage<T, N> {
+        MovingAverage::new()
+    }
+}
+
+impl<T: Copy + Into<f64>, const N: usize> Extend<T> for MovingAverage<T, N> {
+    /// Pushes every sample of an iterator
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for sample in iter {
+            self.push(sample);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MovingAverage;
+
+    #[test]
+    fn average_partial_then_full() {
+        let mut filter: MovingAverage<u32, 3> = MovingAverage::new();
+
+        assert_eq!(filter.push(u32::MAX), None);
+        assert_eq!(filter.average(), Some(u32::MAX as f64));
+        filter.push(u32::MAX);
+        filter.push(0);
+        assert_eq!(filter.sum(), 2.0 * u32::MAX as f64);
+        assert_eq!(filter.push(3), Some(u32::MAX));
+        assert_eq!(filter.average(), Some((u32::MAX as f64 + 3.0) / 3.0));
+
+        filter.clear();
+        assert_eq!(filter.average(), None);
+        filter.push(6);
+        assert_eq!(filter.average(), Some(6.0));
+    }
+}
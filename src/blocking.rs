@@ -0,0 +1,241 @@
+//! A ring buffer whose reads and writes wait for data or space
+use std::fmt;
+use std::sync::{Condvar, Mutex, PoisonError};
+use std::time::Duration;
+
+use crate::policy::Reject;
+use crate::sync::lock_ignoring_poison;
+use crate::{Full, RingBuffer, Timeout};
+
+/// A fixed-capacity FIFO ring buffer holding up to `N` elements that threads share by reference
+/// and that can park a thread until an element or free space is available
+///
+/// The ring buffer sits behind a mutex, and two condition variables wake readers when an
+/// element is written and writers when one is read. Writes never overwrite unread data
+///
+/// # Examples
+///
+/// ```
+/// let ring_buffer: sringbuf::BlockingRingBuffer<u32, 2> = sringbuf::BlockingRingBuffer::new();
+///
+/// std::thread::scope(|scope| {
+///     scope.spawn(|| {
+///         for i in 0..10 {
+///             ring_buffer.write_blocking(i);
+///         }
+///     });
+///
+///     for i in 0..10 {
+///         assert_eq!(ring_buffer.read_blocking(), i);
+///     }
+/// });
+/// ```
+pub struct BlockingRingBuffer<T, const N: usize> {
+    ring_buffer: Mutex<RingBuffer<T, N, Reject>>,
+    readable: Condvar,
+    writable: Condvar,
+}
+
+impl<T, const N: usize> BlockingRingBuffer<T, N> {
+    /// Creates a new BlockingRingBuffer instance
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// static RING_BUFFER: sringbuf::BlockingRingBuffer<u8, 5> = sringbuf::BlockingRingBuffer::new();
+    /// ```
+    pub const fn new() -> BlockingRingBuffer<T, N> {
+        BlockingRingBuffer {
+            ring_buffer: Mutex::new(RingBuffer::new()),
+            readable: Condvar::new(),
+            writable: Condvar::new(),
+        }
+    }
+
+    /// Writes a value to a ring buffer, parking the thread until there is free space
+    pub fn write_blocking(&self, data: T) {
+        let ring_buffer = lock_ignoring_poison(&self.ring_buffer);
+        let mut ring_buffer = self
+            .writable
+            .wait_while(ring_buffer, |ring_buffer| ring_buffer.is_full())
+            .unwrap_or_else(PoisonError::into_inner);
+
+        ring_buffer.write(data);
+        self.readable.notify_one();
+    }
+
+    /// Writes a value to a ring buffer, parking the thread for at most `timeout` until there is
+    /// free space and handing the value back in [`Timeout`] if there still is none
+    pub fn write_blocking_timeout(&self, data: T, timeout: Duration) -> Result<(), Timeout<T>> {
+        let ring_buffer = lock_ignoring_poison(&self.ring_buffer);
+        let (mut ring_buffer, _) = self
+            .writable
+            .wait_timeout_while(ring_buffer, timeout, |ring_buffer| ring_buffer.is_full())
+            .unwrap_or_else(PoisonError::into_inner);
+
+        if ring_buffer.is_full() {
+            return Err(Timeout(data));
+        }
+
+        ring_buffer.write(data);
+        self.readable.notify_one();
+        Ok(())
+    }
+
+    /// Reads the oldest element from a ring buffer, parking the thread until there is one
+    pub fn read_blocking(&self) -> T {
+        let ring_buffer = lock_ignoring_poison(&self.ring_buffer);
+        let mut ring_buffer = self
+            .readable
+            .wait_while(ring_buffer, |ring_buffer| ring_buffer.is_empty())
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let data = ring_buffer.read();
+        self.writable.notify_one();
+        data.expect("woken with an element available")
+    }
+
+    /// Reads the oldest element from a ring buffer, parking the thread for at most `timeout`
+    /// until there is one
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let ring_buffer: sringbuf::BlockingRingBuffer<u8, 1> = sringbuf::BlockingRingBuffer::new();
+    /// assert_eq!(ring_buffer.read_blocking_timeout(Duration::from_millis(1)), Err(sringbuf::Timeout(())));
+    /// ```
+    pub fn read_blocking_timeout(&self, timeout: Duration) -> Result<T, Timeout> {
+        let ring_buffer = lock_ignoring_poison(&self.ring_buffer);
+        let (mut ring_buffer, _) = self
+            .readable
+            .wait_timeout_while(ring_buffer, timeout, |ring_buffer| ring_buffer.is_empty())
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let data = ring_buffer.read().ok_or(Timeout(()))?;
+        self.writable.notify_one();
+        Ok(data)
+    }
+
+    /// Writes a value to a ring buffer if it has free space, handing the value back in
+    /// [`Full`] otherwise
+    pub fn try_write(&self, data: T) -> Result<(), Full<T>> {
+        lock_ignoring_poison(&self.ring_buffer).try_write(data)?;
+        self.readable.notify_one();
+        Ok(())
+    }
+
+    /// Reads the oldest element from a ring buffer, or returns `None` if it is empty
+    pub fn read(&self) -> Option<T> {
+        let data = lock_ignoring_poison(&self.ring_buffer).read()?;
+        self.writable.notify_one();
+        Some(data)
+    }
+
+    /// Returns the number of elements in a ring buffer
+    pub fn len(&self) -> usize {
+        lock_ignoring_poison(&self.ring_buffer).len()
+    }
+
+    /// Returns `true` if a ring buffer holds no elements
+    pub fn is_empty(&self) -> bool {
+        lock_ignoring_poison(&self.ring_buffer).is_empty()
+    }
+
+    /// Returns `true` if a ring buffer holds `N` elements
+    pub fn is_full(&self) -> bool {
+        lock_ignoring_poison(&self.ring_buffer).is_full()
+    }
+
+    /// Returns the maximum number of elements a ring buffer can hold
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the underlying ring buffer
+    pub fn into_inner(self) -> RingBuffer<T, N, Reject> {
+        self.ring_buffer
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T, const N: usize> Default for BlockingRingBuffer<T, N> {
+    /// Creates an empty ring buffer
+    fn default() -> BlockingRingBuffer<T, N> {
+        BlockingRingBuffer::new()
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for BlockingRingBuffer<T, N>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("BlockingRingBuffer")
+            .field(&*lock_ignoring_poison(&self.ring_buffer))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BlockingRingBuffer, Full, Timeout};
+
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn write_blocking_waits_for_reader() {
+        const COUNT: u32 = if cfg!(miri) { 50 } else { 10_000 };
+
+        let ring_buffer: BlockingRingBuffer<u32, 3> = BlockingRingBuffer::new();
+
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                for i in 0..COUNT {
+                    ring_buffer.write_blocking(i);
+                }
+            });
+
+            for i in 0..COUNT {
+                assert_eq!(ring_buffer.read_blocking(), i);
+            }
+        });
+        assert!(ring_buffer.is_empty());
+    }
+
+    #[test]
+    fn write_blocking_timeout_full() {
+        let ring_buffer: BlockingRingBuffer<u8, 1> = BlockingRingBuffer::new();
+
+        assert_eq!(
+            ring_buffer.write_blocking_timeout(1, Duration::from_millis(1)),
+            Ok(())
+        );
+        assert_eq!(
+            ring_buffer.write_blocking_timeout(2, Duration::from_millis(1)),
+            Err(Timeout(2))
+        );
+        assert_eq!(ring_buffer.try_write(3), Err(Full(3)));
+        assert_eq!(ring_buffer.read(), Some(1));
+    }
+
+    #[test]
+    fn read_blocking_timeout_woken_by_writer() {
+        let ring_buffer: BlockingRingBuffer<u8, 1> = BlockingRingBuffer::new();
+
+        thread::scope(|scope| {
+            scope.spawn(|| ring_buffer.write_blocking(1));
+            assert_eq!(
+                ring_buffer.read_blocking_timeout(Duration::from_secs(60)),
+                Ok(1)
+            );
+        });
+        assert_eq!(
+            ring_buffer.read_blocking_timeout(Duration::ZERO),
+            Err(Timeout(()))
+        );
+    }
+}
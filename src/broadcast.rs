@@ -0,0 +1,483 @@
+//! A bounded broadcast queue where every receiver sees every element
+//!
+//! [`channel`] returns a [`Sender`] and a first [`Receiver`]. Each receiver holds its own read
+//! cursor, and an element is kept until every receiver subscribed when it was sent has read it.
+//! What happens when a slow receiver holds up a full queue is chosen through the
+//! [`policy`](crate::policy) type parameter: with [`policy::Overwrite`] the oldest element is
+//! overwritten and the receivers that missed it get [`TryRecvError::Lagged`], while
+//! [`policy::Reject`] makes [`Sender::send`] fail until the slowest receiver catches up
+//!
+//! # Examples
+//!
+//! ```
+//! use sringbuf::policy::Overwrite;
+//!
+//! let (sender, mut first) = sringbuf::broadcast::channel::<u8, 4, Overwrite>();
+//! let mut second = sender.subscribe();
+//!
+//! sender.send(1).unwrap();
+//! sender.send(2).unwrap();
+//! assert_eq!(first.try_recv(), Ok(1));
+//! assert_eq!(first.try_recv(), Ok(2));
+//! assert_eq!(second.try_recv(), Ok(1));
+//! ```
+//!
+//! [`policy::Overwrite`]: crate::policy::Overwrite
+//! [`policy::Reject`]: crate::policy::Reject
+use std::error::Error;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use crate::policy::sealed::Overflow;
+use crate::policy::{OverflowPolicy, Overwrite};
+use crate::sync::lock_ignoring_poison;
+use crate::{Full, RingBuffer};
+
+/// An element along with the number of receivers that have yet to read it
+struct Entry<T> {
+    data: T,
+    remaining: usize,
+}
+
+/// The elements of a broadcast queue and the sequence number of the oldest one
+///
+/// A receiver's cursor is the sequence number of the next element it reads, so cursors stay
+/// meaningful after the elements they point at have been reclaimed or overwritten
+struct State<T, const N: usize, P> {
+    ring_buffer: RingBuffer<Entry<T>, N, P>,
+    head: u64,
+    receivers: usize,
+    closed: bool,
+}
+
+impl<T, const N: usize, P> State<T, N, P> {
+    /// Returns the sequence number the next element sent will get
+    fn tail(&self) -> u64 {
+        self.head + self.ring_buffer.len() as u64
+    }
+
+    /// Returns the index in the ring buffer of the first element a cursor has yet to read
+    fn index(&self, cursor: u64) -> usize {
+        cursor.saturating_sub(self.head) as usize
+    }
+
+    /// Drops the oldest elements that every receiver has read
+    fn reclaim(&mut self) {
+        while self
+            .ring_buffer
+            .peek()
+            .is_some_and(|entry| entry.remaining == 0)
+        {
+            self.ring_buffer.read();
+            self.head += 1;
+        }
+    }
+}
+
+/// The sending half of a broadcast queue
+pub struct Sender<T, const N: usize, P = Overwrite> {
+    state: Arc<Mutex<State<T, N, P>>>,
+}
+
+/// A subscriber to a broadcast queue, reading every element sent after it subscribed
+///
+/// Cloning a receiver creates a subscriber with the same cursor, which reads the same elements
+/// from then on
+pub struct Receiver<T, const N: usize, P = Overwrite> {
+    state: Arc<Mutex<State<T, N, P>>>,
+    cursor: u64,
+}
+
+/// Creates a broadcast queue holding up to `N` elements, returning its sender and a first
+/// receiver
+///
+/// A capacity of zero is rejected at compile time
+///
+/// # Examples
+///
+/// ```
+/// use sringbuf::policy::Reject;
+///
+/// let (sender, mut receiver) = sringbuf::broadcast::channel::<u8, 1, Reject>();
+/// assert_eq!(sender.send(1), Ok(()));
+/// assert_eq!(sender.send(2), Err(sringbuf::Full(2)));
+/// assert_eq!(receiver.try_recv(), Ok(1));
+/// assert_eq!(sender.send(2), Ok(()));
+/// ```
+pub fn channel<T, const N: usize, P>() -> (Sender<T, N, P>, Receiver<T, N, P>) {
+    let state = Arc::new(Mutex::new(State {
+        ring_buffer: RingBuffer::new(),
+        head: 0,
+        receivers: 1,
+        closed: false,
+    }));
+
+    (
+        Sender {
+            state: Arc::clone(&state),
+        },
+        Receiver { state, cursor: 0 },
+    )
+}
+
+impl<T, const N: usize, P> Sender<T, N, P> {
+    /// Sends a value to every current receiver
+    ///
+    /// If the queue is full, the outcome depends on its policy: [`Overwrite`] replaces the
+    /// oldest element, [`policy::Reject`](crate::policy::Reject) hands `data` back in [`Full`]
+    /// and [`policy::Panic`](crate::policy::Panic) panics. A value sent while there are no
+    /// receivers is dropped
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sringbuf::policy::Overwrite;
+    ///
+    /// let (sender, mut receiver) = sringbuf::broadcast::channel::<u8, 2, Overwrite>();
+    /// sender.send(1).unwrap();
+    /// assert_eq!(receiver.try_recv(), Ok(1));
+    /// ```
+    pub fn send(&self, data: T) -> Result<(), Full<T>>
+    where
+        P: OverflowPolicy,
+    {
+        let mut state = lock_ignoring_poison(&self.state);
+        if state.ring_buffer.is_full() && matches!(P::OVERFLOW, Overflow::Reject) {
+            return Err(Full(data));
+        }
+
+        let remaining = state.receivers;
+        if state.ring_buffer.write(Entry { data, remaining }).is_some() {
+            state.head += 1;
+        }
+        state.reclaim();
+        Ok(())
+    }
+
+    /// Creates a receiver that reads every element sent from now on
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sringbuf::policy::Overwrite;
+    ///
+    /// let (sender, _receiver) = sringbuf::broadcast::channel::<u8, 2, Overwrite>();
+    /// sender.send(1).unwrap();
+    ///
+    /// let mut late = sender.subscribe();
+    /// sender.send(2).unwrap();
+    /// assert_eq!(late.try_recv(), Ok(2));
+    /// ```
+    pub fn subscribe(&self) -> Receiver<T, N, P> {
+        let mut state = lock_ignoring_poison(&self.state);
+        state.receivers += 1;
+
+        Receiver {
+            state: Arc::clone(&self.state),
+            cursor: state.tail(),
+        }
+    }
+
+    /// Returns the number of receivers currently subscribed
+    pub fn receiver_count(&self) -> usize {
+        lock_ignoring_poison(&self.state).receivers
+    }
+
+    /// Returns the number of elements held for receivers that have yet to read them
+    pub fn len(&self) -> usize {
+        lock_ignoring_poison(&self.state).ring_buffer.len()
+    }
+
+    /// Returns `true` if every receiver has read every element sent
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the maximum number of elements a broadcast queue can hold
+    pub fn capacity(&self) -> usize {
+        N
+    }
+}
+
+impl<T, const N: usize, P> Receiver<T, N, P> {
+    /// Reads the next element this receiver has not seen, without blocking
+    ///
+    /// The element is cloned unless this receiver is the last one to read it. If elements
+    /// were overwritten before this receiver read them, [`TryRecvError::Lagged`] reports how
+    /// many and the cursor skips to the oldest element still held
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sringbuf::broadcast::TryRecvError;
+    /// use sringbuf::policy::Overwrite;
+    ///
+    /// let (sender, mut receiver) = sringbuf::broadcast::channel::<u8, 2, Overwrite>();
+    /// for i in 0..5 {
+    ///     sender.send(i).unwrap();
+    /// }
+    /// assert_eq!(receiver.try_recv(), Err(TryRecvError::Lagged(3)));
+    /// assert_eq!(receiver.try_recv(), Ok(3));
+    /// assert_eq!(receiver.try_recv(), Ok(4));
+    /// assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+    /// drop(sender);
+    /// assert_eq!(receiver.try_recv(), Err(TryRecvError::Closed));
+    /// ```
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError>
+    where
+        T: Clone,
+    {
+        let mut state = lock_ignoring_poison(&self.state);
+        if self.cursor < state.head {
+            let skipped = state.head - self.cursor;
+            self.cursor = state.head;
+            return Err(TryRecvError::Lagged(skipped));
+        }
+
+        if self.cursor == state.tail() {
+            return Err(if state.closed {
+                TryRecvError::Closed
+            } else {
+                TryRecvError::Empty
+            });
+        }
+
+        let index = state.index(self.cursor);
+        self.cursor += 1;
+        let entry = &mut state.ring_buffer[index];
+        entry.remaining -= 1;
+        if entry.remaining > 0 {
+            return Ok(entry.data.clone());
+        }
+
+        // Earlier elements have no more readers than later ones, so an element nobody else
+        // needs is always the oldest
+        let entry = state.ring_buffer.read().unwrap();
+        state.head += 1;
+        Ok(entry.data)
+    }
+
+    /// Returns the number of elements this receiver can still read
+    pub fn len(&self) -> usize {
+        let state = lock_ignoring_poison(&self.state);
+        state.ring_buffer.len() - state.index(self.cursor)
+    }
+
+    /// Returns `true` if this receiver has read every element still held
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the sender has been dropped
+    pub fn is_closed(&self) -> bool {
+        lock_ignoring_poison(&self.state).closed
+    }
+}
+
+impl<T, const N: usize, P> Drop for Sender<T, N, P> {
+    /// Closes the queue, so receivers see [`TryRecvError::Closed`] once they have read
+    /// everything
+    fn drop(&mut self) {
+        lock_ignoring_poison(&self.state).closed = true;
+    }
+}
+
+impl<T, const N: usize, P> Clone for Receiver<T, N, P> {
+    /// Creates a receiver with the same cursor
+    fn clone(&self) -> Receiver<T, N, P> {
+        let mut state = lock_ignoring_poison(&self.state);
+        let index = state.index(self.cursor);
+        for entry in state.ring_buffer.iter_mut().skip(index) {
+            entry.remaining += 1;
+        }
+        state.receivers += 1;
+
+        Receiver {
+            state: Arc::clone(&self.state),
+            cursor: self.cursor,
+        }
+    }
+}
+
+impl<T, const N: usize, P> Drop for Receiver<T, N, P> {
+    /// Unsubscribes the receiver, reclaiming the elements only it had yet to read
+    fn drop(&mut self) {
+        let mut state = lock_ignoring_poison(&self.state);
+        let index = state.index(self.cursor);
+        for entry in state.ring_buffer.iter_mut().skip(index) {
+            entry.remaining -= 1;
+        }
+        state.receivers -= 1;
+        state.reclaim();
+    }
+}
+
+impl<T, const N: usize, P> fmt::Debug for Sender<T, N, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").finish_non_exhaustive()
+    }
+}
+
+impl<T, const N: usize, P> fmt::Debug for Receiver<T, N, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver")
+            .field("cursor", &self.cursor)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The error returned by [`Receiver::try_recv`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TryRecvError {
+    /// The receiver has read every element sent so far
+    Empty,
+    /// The given number of elements were overwritten before the receiver read them
+    Lagged(u64),
+    /// The receiver has read every element and the sender has been dropped
+    Closed,
+}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryRecvError::Empty => f.write_str("receiving on an empty broadcast queue"),
+            TryRecvError::Lagged(skipped) => write!(f, "receiver lagged by {skipped} elements"),
+            TryRecvError::Closed => f.write_str("receiving on a closed broadcast queue"),
+        }
+    }
+}
+
+impl Error for TryRecvError {}
+
+#[cfg(test)]
+mod tests {
+    use crate::Full;
+    use crate::broadcast::{TryRecvError, channel};
+    use crate::policy::{Overwrite, Reject};
+
+    use std::rc::Rc;
+    use std::thread;
+
+    #[test]
+    fn try_recv_every_receiver() {
+        let (sender, mut first) = channel::<u8, 3, Overwrite>();
+        let mut second = first.clone();
+
+        for i in 0..3 {
+            sender.send(i).unwrap();
+        }
+        assert_eq!(first.try_recv(), Ok(0));
+        assert_eq!(first.try_recv(), Ok(1));
+        assert_eq!(second.try_recv(), Ok(0));
+        assert_eq!(sender.len(), 2);
+        assert_eq!(first.len(), 1);
+        assert_eq!(second.len(), 2);
+    }
+
+    #[test]
+    fn send_reject_until_slowest_reads() {
+        let (sender, mut fast) = channel::<u8, 2, Reject>();
+        let mut slow = sender.subscribe();
+
+        sender.send(1).unwrap();
+        sender.send(2).unwrap();
+        assert_eq!(fast.try_recv(), Ok(1));
+        assert_eq!(fast.try_recv(), Ok(2));
+        assert_eq!(sender.send(3), Err(Full(3)));
+
+        assert_eq!(slow.try_recv(), Ok(1));
+        assert_eq!(sender.send(3), Ok(()));
+        assert_eq!(sender.send(4), Err(Full(4)));
+    }
+
+    #[test]
+    fn try_recv_lagged() {
+        let (sender, mut receiver) = channel::<u32, 3, Overwrite>();
+
+        for i in 0..10 {
+            sender.send(i).unwrap();
+        }
+        assert_eq!(receiver.try_recv(), Err(TryRecvError::Lagged(7)));
+        assert_eq!(receiver.try_recv(), Ok(7));
+        assert_eq!(receiver.len(), 2);
+    }
+
+    #[test]
+    fn subscribe_skips_earlier_elements() {
+        let (sender, _receiver) = channel::<u8, 3, Overwrite>();
+        sender.send(1).unwrap();
+
+        let mut late = sender.subscribe();
+        assert_eq!(late.try_recv(), Err(TryRecvError::Empty));
+        sender.send(2).unwrap();
+        assert_eq!(late.try_recv(), Ok(2));
+        assert_eq!(sender.receiver_count(), 2);
+    }
+
+    #[test]
+    fn drop_receiver_reclaims() {
+        let counter = Rc::new(());
+        let (sender, mut fast) = channel::<Rc<()>, 3, Reject>();
+        let slow = fast.clone();
+
+        sender.send(Rc::clone(&counter)).unwrap();
+        sender.send(Rc::clone(&counter)).unwrap();
+        drop(fast.try_recv());
+        assert_eq!(Rc::strong_count(&counter), 3);
+
+        drop(slow);
+        assert_eq!(Rc::strong_count(&counter), 2);
+        assert_eq!(sender.len(), 1);
+
+        drop(fast);
+        assert!(sender.is_empty());
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn send_without_receivers() {
+        let counter = Rc::new(());
+        let (sender, receiver) = channel::<Rc<()>, 2, Reject>();
+
+        drop(receiver);
+        assert_eq!(sender.send(Rc::clone(&counter)), Ok(()));
+        assert!(sender.is_empty());
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn threads_see_every_element() {
+        const COUNT: u32 = if cfg!(miri) { 50 } else { 10_000 };
+
+        let (sender, receiver) = channel::<u32, 4, Reject>();
+        let readers: Vec<_> = (0..3)
+            .map(|_| {
+                let mut receiver = receiver.clone();
+                thread::spawn(move || {
+                    let mut received = Vec::new();
+                    loop {
+                        match receiver.try_recv() {
+                            Ok(data) => received.push(data),
+                            Err(TryRecvError::Closed) => return received,
+                            Err(_) => thread::yield_now(),
+                        }
+                    }
+                })
+            })
+            .collect();
+        drop(receiver);
+
+        for i in 0..COUNT {
+            let mut data = i;
+            while let Err(Full(rejected)) = sender.send(data) {
+                data = rejected;
+                thread::yield_now();
+            }
+        }
+        drop(sender);
+
+        for reader in readers {
+            assert!(reader.join().unwrap().into_iter().eq(0..COUNT));
+        }
+    }
+}
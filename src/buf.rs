@@ -0,0 +1,171 @@
+//! [`bytes`] [`Buf`] and [`BufMut`] implementations for byte ring buffers and the SPSC halves
+use bytes::buf::UninitSlice;
+use bytes::{Buf, BufMut};
+
+#[cfg(target_has_atomic = "ptr")]
+use crate::spsc::{Consumer, Producer};
+use crate::{Cursor, GenericRingBuffer, Storage};
+
+impl<S, P, C> Buf for GenericRingBuffer<u8, S, P, C>
+where
+    S: Storage<u8>,
+    C: Cursor,
+{
+    /// Returns the number of bytes left to read, unlike the inherent
+    /// [`remaining`](GenericRingBuffer::remaining) which counts free slots
+    fn remaining(&self) -> usize {
+        self.len()
+    }
+
+    /// Returns the oldest contiguous run of bytes, which ends at the wrap point if the
+    /// contents wrap around
+    fn chunk(&self) -> &[u8] {
+        self.as_slices().0
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        assert!(cnt <= self.len(), "advance past the end of the ring buffer");
+
+        self.set_read_index(self.slot_index(cnt));
+        self.set_len(self.len() - cnt);
+    }
+}
+
+// SAFETY: `chunk_mut` only exposes free slots, and `advance_mut` marks at most that many of them
+// as occupied
+unsafe impl<S, P, C> BufMut for GenericRingBuffer<u8, S, P, C>
+where
+    S: Storage<u8>,
+    C: Cursor,
+{
+    fn remaining_mut(&self) -> usize {
+        self.capacity() - self.len()
+    }
+
+    /// Returns the free run of slots after the newest byte, which ends at the wrap point
+    fn chunk_mut(&mut self) -> &mut UninitSlice {
+        UninitSlice::uninit(self.free_slots_mut().0)
+    }
+
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        assert!(
+            cnt <= self.capacity() - self.len(),
+            "advance past the free space of the ring buffer"
+        );
+
+        self.set_len(self.len() + cnt);
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<const N: usize> Buf for Consumer<'_, u8, N> {
+    fn remaining(&self) -> usize {
+        self.len()
+    }
+
+    /// Returns the oldest queued bytes that are contiguous in memory; more may follow once they
+    /// are consumed
+    fn chunk(&self) -> &[u8] {
+        self.readable_slice()
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        self.release(cnt);
+    }
+}
+
+// SAFETY: `chunk_mut` only exposes free slots, and `publish` checks that at most that many are
+// handed to the consumer
+#[cfg(target_has_atomic = "ptr")]
+unsafe impl<const N: usize> BufMut for Producer<'_, u8, N> {
+    fn remaining_mut(&self) -> usize {
+        N - self.len()
+    }
+
+    /// Returns the free slots after the newest byte that are contiguous in memory
+    fn chunk_mut(&mut self) -> &mut UninitSlice {
+        UninitSlice::uninit(self.writable_slots())
+    }
+
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        // SAFETY: the caller initialized the first `cnt` bytes of `chunk_mut`
+        unsafe { self.publish(cnt) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RingBuffer;
+    use crate::spsc::Queue;
+
+    use bytes::{Buf, BufMut};
+    use std::thread;
+
+    #[test]
+    fn ring_buffer_put_and_get_wrap_around() {
+        let mut ring_buffer: RingBuffer<u8, 8> = RingBuffer::new();
+
+        ring_buffer.put_slice(b"abcde");
+        assert_eq!(ring_buffer.get_u16(), u16::from_be_bytes(*b"ab"));
+        ring_buffer.put_u32_le(0x0403_0201);
+        assert_eq!(BufMut::remaining_mut(&ring_buffer), 1);
+        assert_eq!(Buf::remaining(&ring_buffer), 7);
+        assert_eq!(ring_buffer.chunk(), b"cde\x01\x02\x03");
+
+        let mut output = Vec::new();
+        output.put(&mut ring_buffer);
+        assert_eq!(output, b"cde\x01\x02\x03\x04");
+        assert!(ring_buffer.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn ring_buffer_advance_invalid_input() {
+        let mut ring_buffer: RingBuffer<u8, 4> = RingBuffer::new();
+        ring_buffer.put_u8(1);
+        ring_buffer.advance(2);
+    }
+
+    #[test]
+    fn split_put_and_copy_to_bytes() {
+        let mut queue: Queue<u8, 4> = Queue::new();
+        let (mut producer, mut consumer) = queue.split();
+
+        producer.put_slice(b"abc");
+        assert_eq!(consumer.copy_to_bytes(2), &b"ab"[..]);
+        producer.put_slice(b"def");
+        assert_eq!(producer.remaining_mut(), 0);
+        assert_eq!(consumer.chunk(), b"cd");
+        consumer.advance(2);
+        assert_eq!(consumer.chunk(), b"ef");
+    }
+
+    #[test]
+    fn split_threads_copy_everything() {
+        const COUNT: usize = if cfg!(miri) { 100 } else { 10_000 };
+
+        let mut queue: Queue<u8, 16> = Queue::new();
+        let (mut producer, mut consumer) = queue.split();
+        let data: Vec<u8> = (0..COUNT).map(|i| i as u8).collect();
+
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                let mut src = &data[..];
+                while src.has_remaining() {
+                    let count = src.len().min(producer.remaining_mut());
+                    producer.put(src.take(count));
+                    src.advance(count);
+                    thread::yield_now();
+                }
+            });
+
+            let mut received = Vec::new();
+            while received.len() < COUNT {
+                let count = consumer.remaining();
+                received.put((&mut consumer).take(count));
+                thread::yield_now();
+            }
+            assert_eq!(received, data);
+        });
+    }
+}
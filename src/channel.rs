@@ -0,0 +1,544 @@
+//! A bounded channel with the interface of [`std::sync::mpsc::sync_channel`]
+//!
+//! [`channel`] returns a [`Sender`], which can be cloned for every sending thread, and a
+//! [`Receiver`] sharing a ring buffer of `N` elements. Sends block while the ring buffer is
+//! full, receives block while it is empty, and either side learns when the other has hung up
+//!
+//! # Examples
+//!
+//! ```
+//! let (sender, receiver) = sringbuf::channel::channel::<u32, 4>();
+//!
+//! for id in 0..2 {
+//!     let sender = sender.clone();
+//!     std::thread::spawn(move || {
+//!         for i in 0..5 {
+//!             sender.send(id * 10 + i).unwrap();
+//!         }
+//!     });
+//! }
+//! drop(sender);
+//!
+//! let mut received: Vec<u32> = receiver.iter().collect();
+//! received.sort();
+//! assert_eq!(received, [0, 1, 2, 3, 4, 10, 11, 12, 13, 14]);
+//! ```
+use std::error::Error;
+use std::fmt;
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::RingBuffer;
+use crate::policy::Reject;
+use crate::sync::lock_ignoring_poison;
+
+/// The ring buffer of a channel along with who is still connected to it
+struct State<T, const N: usize> {
+    ring_buffer: RingBuffer<T, N, Reject>,
+    senders: usize,
+    receiver: bool,
+}
+
+/// The state shared by every handle of a channel
+struct Channel<T, const N: usize> {
+    state: Mutex<State<T, N>>,
+    readable: Condvar,
+    writable: Condvar,
+}
+
+/// The sending half of a channel, which can be cloned to send from several threads
+pub struct Sender<T, const N: usize> {
+    channel: Arc<Channel<T, N>>,
+}
+
+/// The receiving half of a channel
+pub struct Receiver<T, const N: usize> {
+    channel: Arc<Channel<T, N>>,
+}
+
+/// Creates a channel buffering up to `N` elements, returning its sending and receiving halves
+///
+/// A capacity of zero is rejected at compile time
+///
+/// # Examples
+///
+/// ```
+/// let (sender, receiver) = sringbuf::channel::channel::<u8, 1>();
+/// sender.send(1).unwrap();
+/// assert_eq!(receiver.recv(), Ok(1));
+/// ```
+pub fn channel<T, const N: usize>() -> (Sender<T, N>, Receiver<T, N>) {
+    let channel = Arc::new(Channel {
+        state: Mutex::new(State {
+            ring_buffer: RingBuffer::new(),
+            senders: 1,
+            receiver: true,
+        }),
+        readable: Condvar::new(),
+        writable: Condvar::new(),
+    });
+
+    (
+        Sender {
+            channel: Arc::clone(&channel),
+        },
+        Receiver { channel },
+    )
+}
+
+impl<T, const N: usize> Sender<T, N> {
+    /// Sends a value, blocking while the channel is full
+    ///
+    /// Fails, handing the value back, once the receiver has been dropped
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let (sender, receiver) = sringbuf::channel::channel::<u8, 1>();
+    /// assert_eq!(sender.send(1), Ok(()));
+    /// drop(receiver);
+    /// assert_eq!(sender.send(2), Err(sringbuf::channel::SendError(2)));
+    /// ```
+    pub fn send(&self, data: T) -> Result<(), SendError<T>> {
+        let state = lock_ignoring_poison(&self.channel.state);
+        let mut state = self
+            .channel
+            .writable
+            .wait_while(state, |state| state.receiver && state.ring_buffer.is_full())
+            .unwrap_or_else(PoisonError::into_inner);
+
+        if !state.receiver {
+            return Err(SendError(data));
+        }
+
+        state.ring_buffer.write(data);
+        self.channel.readable.notify_one();
+        Ok(())
+    }
+
+    /// Sends a value if the channel has free space, without blocking
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sringbuf::channel::TrySendError;
+    ///
+    /// let (sender, _receiver) = sringbuf::channel::channel::<u8, 1>();
+    /// assert_eq!(sender.try_send(1), Ok(()));
+    /// assert_eq!(sender.try_send(2), Err(TrySendError::Full(2)));
+    /// ```
+    pub fn try_send(&self, data: T) -> Result<(), TrySendError<T>> {
+        let mut state = lock_ignoring_poison(&self.channel.state);
+        if !state.receiver {
+            return Err(TrySendError::Disconnected(data));
+        }
+
+        state
+            .ring_buffer
+            .try_write(data)
+            .map_err(|full| TrySendError::Full(full.into_inner()))?;
+        self.channel.readable.notify_one();
+        Ok(())
+    }
+}
+
+impl<T, const N: usize> Receiver<T, N> {
+    /// Receives the oldest value, blocking while the channel is empty
+    ///
+    /// Fails once the channel is empty and every sender has been dropped
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let (sender, receiver) = sringbuf::channel::channel::<u8, 1>();
+    /// sender.send(1).unwrap();
+    /// drop(sender);
+    /// assert_eq!(receiver.recv(), Ok(1));
+    /// assert_eq!(receiver.recv(), Err(sringbuf::channel::RecvError));
+    /// ```
+    pub fn recv(&self) -> Result<T, RecvError> {
+        let state = lock_ignoring_poison(&self.channel.state);
+        let mut state = self
+            .channel
+            .readable
+            .wait_while(state, |state| {
+                state.senders > 0 && state.ring_buffer.is_empty()
+            })
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let data = state.ring_buffer.read().ok_or(RecvError)?;
+        self.channel.writable.notify_one();
+        Ok(data)
+    }
+
+    /// Receives the oldest value if there is one, without blocking
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sringbuf::channel::TryRecvError;
+    ///
+    /// let (sender, receiver) = sringbuf::channel::channel::<u8, 1>();
+    /// assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+    /// drop(sender);
+    /// assert_eq!(receiver.try_recv(), Err(TryRecvError::Disconnected));
+    /// ```
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        let mut state = lock_ignoring_poison(&self.channel.state);
+        match state.ring_buffer.read() {
+            Some(data) => {
+                self.channel.writable.notify_one();
+                Ok(data)
+            }
+            None if state.senders == 0 => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    /// Receives the oldest value, blocking for at most `timeout` while the channel is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use sringbuf::channel::RecvTimeoutError;
+    ///
+    /// let (_sender, receiver) = sringbuf::channel::channel::<u8, 1>();
+    /// assert_eq!(receiver.recv_timeout(Duration::from_millis(1)), Err(RecvTimeoutError::Timeout));
+    /// ```
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        let deadline = Instant::now() + timeout;
+        let mut state = lock_ignoring_poison(&self.channel.state);
+
+        loop {
+            if let Some(data) = state.ring_buffer.read() {
+                self.channel.writable.notify_one();
+                return Ok(data);
+            }
+            if state.senders == 0 {
+                return Err(RecvTimeoutError::Disconnected);
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(RecvTimeoutError::Timeout);
+            }
+
+            state = self
+                .channel
+                .readable
+                .wait_timeout(state, deadline - now)
+                .unwrap_or_else(PoisonError::into_inner)
+                .0;
+        }
+    }
+
+    /// Returns an iterator that receives values, blocking for each, until every sender has
+    /// been dropped
+    pub fn iter(&self) -> Iter<'_, T, N> {
+        Iter { receiver: self }
+    }
+
+    /// Returns an iterator over the values that can be received without blocking
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let (sender, receiver) = sringbuf::channel::channel::<u8, 4>();
+    /// sender.send(1).unwrap();
+    /// sender.send(2).unwrap();
+    /// assert_eq!(receiver.try_iter().collect::<Vec<_>>(), [1, 2]);
+    /// ```
+    pub fn try_iter(&self) -> TryIter<'_, T, N> {
+        TryIter { receiver: self }
+    }
+}
+
+impl<T, const N: usize> Clone for Sender<T, N> {
+    /// Creates another sender for the same channel
+    fn clone(&self) -> Sender<T, N> {
+        lock_ignoring_poison(&self.channel.state).senders += 1;
+
+        Sender {
+            channel: Arc::clone(&self.channel),
+        }
+    }
+}
+
+impl<T, const N: usize> Drop for Sender<T, N> {
+    /// Disconnects the sender, waking the receiver if it was the last one
+    fn drop(&mut self) {
+        let mut state = lock_ignoring_poison(&self.channel.state);
+        state.senders -= 1;
+        if state.senders == 0 {
+            self.channel.readable.notify_all();
+        }
+    }
+}
+
+impl<T, const N: usize> Drop for Receiver<T, N> {
+    /// Disconnects the receiver, waking every blocked sender
+    fn drop(&mut self) {
+        lock_ignoring_poison(&self.channel.state).receiver = false;
+        self.channel.writable.notify_all();
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for Sender<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").finish_non_exhaustive()
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for Receiver<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").finish_non_exhaustive()
+    }
+}
+
+/// An iterator that receives values from a channel, created by [`Receiver::iter`]
+#[derive(Debug)]
+pub struct Iter<'a, T, const N: usize> {
+    receiver: &'a Receiver<T, N>,
+}
+
+impl<T, const N: usize> Iterator for Iter<'_, T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.receiver.recv().ok()
+    }
+}
+
+/// An iterator over the values a channel holds now, created by [`Receiver::try_iter`]
+#[derive(Debug)]
+pub struct TryIter<'a, T, const N: usize> {
+    receiver: &'a Receiver<T, N>,
+}
+
+impl<T, const N: usize> Iterator for TryIter<'_, T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.receiver.try_recv().ok()
+    }
+}
+
+/// An iterator that receives values from a channel it owns
+#[derive(Debug)]
+pub struct IntoIter<T, const N: usize> {
+    receiver: Receiver<T, N>,
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.receiver.recv().ok()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a Receiver<T, N> {
+    type Item = T;
+    type IntoIter = Iter<'a, T, N>;
+
+    fn into_iter(self) -> Iter<'a, T, N> {
+        self.iter()
+    }
+}
+
+impl<T, const N: usize> IntoIterator for Receiver<T, N> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+
+    fn into_iter(self) -> IntoIter<T, N> {
+        IntoIter { receiver: self }
+    }
+}
+
+/// The error returned by [`Sender::send`] once the receiver is gone, holding the value that
+/// could not be sent
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct SendError<T>(pub T);
+
+/// The error returned by [`Sender::try_send`], holding the value that could not be sent
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TrySendError<T> {
+    /// The channel is full
+    Full(T),
+    /// The receiver is gone
+    Disconnected(T),
+}
+
+/// The error returned by [`Receiver::recv`] once the channel is empty and every sender is gone
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RecvError;
+
+/// The error returned by [`Receiver::try_recv`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TryRecvError {
+    /// The channel is empty
+    Empty,
+    /// The channel is empty and every sender is gone
+    Disconnected,
+}
+
+/// The error returned by [`Receiver::recv_timeout`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecvTimeoutError {
+    /// No value arrived before the timeout
+    Timeout,
+    /// The channel is empty and every sender is gone
+    Disconnected,
+}
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SendError").finish_non_exhaustive()
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("sending on a closed channel")
+    }
+}
+
+impl<T> Error for SendError<T> {}
+
+impl<T> fmt::Debug for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrySendError::Full(_) => f.write_str("Full(..)"),
+            TrySendError::Disconnected(_) => f.write_str("Disconnected(..)"),
+        }
+    }
+}
+
+impl<T> fmt::Display for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrySendError::Full(_) => f.write_str("sending on a full channel"),
+            TrySendError::Disconnected(_) => f.write_str("sending on a closed channel"),
+        }
+    }
+}
+
+impl<T> Error for TrySendError<T> {}
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("receiving on a closed channel")
+    }
+}
+
+impl Error for RecvError {}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryRecvError::Empty => f.write_str("receiving on an empty channel"),
+            TryRecvError::Disconnected => f.write_str("receiving on a closed channel"),
+        }
+    }
+}
+
+impl Error for TryRecvError {}
+
+impl fmt::Display for RecvTimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecvTimeoutError::Timeout => f.write_str("timed out waiting on channel"),
+            RecvTimeoutError::Disconnected => f.write_str("channel is empty and disconnected"),
+        }
+    }
+}
+
+impl Error for RecvTimeoutError {}
+
+#[cfg(test)]
+mod tests {
+    use crate::channel::{RecvTimeoutError, SendError, TryRecvError, TrySendError, channel};
+
+    use std::rc::Rc;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn send_blocks_until_received() {
+        const COUNT: u32 = if cfg!(miri) { 50 } else { 10_000 };
+
+        let (sender, receiver) = channel::<u32, 2>();
+        let writer = thread::spawn(move || {
+            for i in 0..COUNT {
+                sender.send(i).unwrap();
+            }
+        });
+
+        assert!(receiver.iter().eq(0..COUNT));
+        writer.join().unwrap();
+    }
+
+    #[test]
+    fn send_unblocks_on_receiver_drop() {
+        let (sender, receiver) = channel::<u8, 1>();
+        sender.send(1).unwrap();
+
+        let writer = thread::spawn(move || sender.send(2));
+        thread::sleep(Duration::from_millis(10));
+        drop(receiver);
+        assert_eq!(writer.join().unwrap(), Err(SendError(2)));
+    }
+
+    #[test]
+    fn try_send_disconnected() {
+        let (sender, receiver) = channel::<u8, 2>();
+        drop(receiver);
+        assert_eq!(sender.try_send(1), Err(TrySendError::Disconnected(1)));
+    }
+
+    #[test]
+    fn recv_drains_before_disconnect() {
+        let (sender, receiver) = channel::<u8, 4>();
+        let second = sender.clone();
+
+        sender.send(1).unwrap();
+        second.send(2).unwrap();
+        drop(sender);
+        assert_eq!(receiver.try_recv(), Ok(1));
+        drop(second);
+        assert_eq!(receiver.recv_timeout(Duration::from_secs(60)), Ok(2));
+        assert_eq!(receiver.try_recv(), Err(TryRecvError::Disconnected));
+        assert_eq!(
+            receiver.recv_timeout(Duration::ZERO),
+            Err(RecvTimeoutError::Disconnected)
+        );
+    }
+
+    #[test]
+    fn into_iter_ends_when_senders_drop() {
+        let (sender, receiver) = channel::<u8, 4>();
+        thread::spawn(move || {
+            for i in 0..8 {
+                sender.send(i).unwrap();
+            }
+        });
+
+        assert_eq!(
+            receiver.into_iter().collect::<Vec<_>>(),
+            (0..8).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn drop_unreceived_values() {
+        let counter = Rc::new(());
+        let (sender, receiver) = channel::<Rc<()>, 2>();
+
+        sender.send(Rc::clone(&counter)).unwrap();
+        sender.send(Rc::clone(&counter)).unwrap();
+        drop(sender);
+        drop(receiver);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+}
@@ -0,0 +1,96 @@
+//! Integer types a ring buffer can store its read cursor and length in
+//!
+//! The cursor type is chosen through the fourth type parameter of
+//! [`GenericRingBuffer`](crate::GenericRingBuffer), which defaults to `usize`. A narrower type
+//! shrinks the bookkeeping of small ring buffers, at the cost of capping their capacity at the
+//! largest value it holds
+//!
+//! # Examples
+//!
+//! ```
+//! use sringbuf::policy::Overwrite;
+//!
+//! let mut ring_buffer: sringbuf::RingBuffer<u8, 32, Overwrite, u8> = sringbuf::RingBuffer::new();
+//! ring_buffer.write(1);
+//! assert_eq!(ring_buffer.read(), Some(1));
+//! assert_eq!(size_of_val(&ring_buffer), 34);
+//! ```
+
+/// An unsigned integer type holding the read cursor and length of a ring buffer
+///
+/// This trait is sealed and implemented by `u8`, `u16`, `u32` and `usize`
+pub trait Cursor: sealed::Sealed + Send + Sync + Unpin {}
+
+impl Cursor for u8 {}
+
+impl Cursor for u16 {}
+
+impl Cursor for u32 {}
+
+impl Cursor for usize {}
+
+pub(crate) mod sealed {
+    pub trait Sealed: Copy {
+        /// The cursor at the first slot
+        const ZERO: Self;
+
+        /// The largest capacity whose length still fits
+        const MAX: usize;
+
+        /// Narrows `value`, which must not exceed [`Sealed::MAX`]
+        fn from_usize(value: usize) -> Self;
+
+        /// Widens the cursor back to a `usize`
+        fn to_usize(self) -> usize;
+    }
+
+    macro_rules! sealed_cursor {
+        ($($ty:ty),*) => {
+            $(
+                impl Sealed for $ty {
+                    const ZERO: $ty = 0;
+
+                    const MAX: usize = if (<$ty>::MAX as u128) < usize::MAX as u128 {
+                        <$ty>::MAX as usize
+                    } else {
+                        usize::MAX
+                    };
+
+                    fn from_usize(value: usize) -> $ty {
+                        debug_assert!(value <= <$ty as Sealed>::MAX);
+                        value as $ty
+                    }
+
+                    fn to_usize(self) -> usize {
+                        self as usize
+                    }
+                }
+            )*
+        };
+    }
+
+    sealed_cursor!(u8, u16, u32, usize);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RingBuffer;
+    use crate::policy::{Overwrite, Reject};
+
+    #[test]
+    fn u8_cursor_wraps() {
+        let mut ring_buffer: RingBuffer<u32, 255, Overwrite, u8> = RingBuffer::new();
+        ring_buffer.extend(0..1000);
+
+        assert_eq!(ring_buffer.len(), 255);
+        assert!(ring_buffer.iter().copied().eq(745..1000));
+        assert_eq!(ring_buffer.get(254), Some(&999));
+    }
+
+    #[test]
+    fn narrow_cursor_size() {
+        assert_eq!(size_of::<RingBuffer<u8, 32, Overwrite, u8>>(), 34);
+        assert_eq!(size_of::<RingBuffer<u8, 32, Reject, u16>>(), 36);
+        assert_eq!(size_of::<RingBuffer<u8, 32>>(), 32 + 2 * size_of::<usize>());
+    }
+}
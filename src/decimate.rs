@@ -0,0 +1,141 @@
+//! A ring buffer that only stores every k-th write
+use core::ops::Deref;
+
+use crate::RingBuffer;
+use crate::policy::{OverflowPolicy, Overwrite};
+
+/// A FIFO ring buffer that accepts every write but only stores one out of every `factor`,
+/// starting with the first
+///
+/// This records a high-rate stream at a reduced rate
+#[derive(Clone, Debug)]
+pub struct DecimatingRingBuffer<T, const N: usize, P = Overwrite> {
+    ring_buffer: RingBuffer<T, N, P>,
+    factor: usize,
+    phase: usize,
+}
+
+impl<T, const N: usize, P> DecimatingRingBuffer<T, N, P> {
+    /// Creates a new DecimatingRingBuffer instance storing one out of every `factor` writes
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let ring_buffer: sringbuf::DecimatingRingBuffer<u8, 5> = sringbuf::DecimatingRingBuffer::new(4);
+    /// assert_eq!(ring_buffer.factor(), 4);
+    /// ```
+    pub const fn new(factor: usize) -> DecimatingRingBuffer<T, N, P> {
+        assert!(factor > 0, "decimation factor must not be zero");
+
+        DecimatingRingBuffer {
+            ring_buffer: RingBuffer::new(),
+            factor,
+            phase: 0,
+        }
+    }
+
+    /// Writes data to a ring buffer if it is due to be stored, or drops it otherwise
+    ///
+    /// A stored value follows the overflow policy `P` as for
+    /// [`GenericRingBuffer::write`](crate::GenericRingBuffer::write). The element that did not
+    /// make it into the ring buffer, either the skipped `data` or the one displaced by it, is
+    /// returned
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::DecimatingRingBuffer<u32, 4> =
+    ///     sringbuf::DecimatingRingBuffer::new(3);
+    /// ring_buffer.extend(0..10);
+    /// assert!(ring_buffer.iter().eq(&[0, 3, 6, 9]));
+    /// ```
+    pub fn write(&mut self, data: T) -> Option<T>
+    where
+        P: OverflowPolicy,
+    {
+        let phase = self.phase;
+        self.phase = (phase + 1) % self.factor;
+        if phase != 0 {
+            return Some(data);
+        }
+
+        self.ring_buffer.write(data)
+    }
+
+    /// Reads the oldest element from a ring buffer, or returns `None` if it is empty
+    pub fn read(&mut self) -> Option<T> {
+        self.ring_buffer.read()
+    }
+
+    /// Returns how many writes it takes to store one element
+    pub fn factor(&self) -> usize {
+        self.factor
+    }
+
+    /// Drops every element of a ring buffer and stores the next write
+    pub fn clear(&mut self) {
+        self.ring_buffer.clear();
+        self.phase = 0;
+    }
+
+    /// Returns the underlying ring buffer
+    pub fn into_inner(self) -> RingBuffer<T, N, P> {
+        self.ring_buffer
+    }
+}
+
+impl<T, const N: usize, P> Deref for DecimatingRingBuffer<T, N, P> {
+    type Target = RingBuffer<T, N, P>;
+
+    fn deref(&self) -> &RingBuffer<T, N, P> {
+        &self.ring_buffer
+    }
+}
+
+impl<T, const N: usize, P: OverflowPolicy> Extend<T> for DecimatingRingBuffer<T, N, P> {
+    /// Writes every element of an iterator to a ring buffer, storing one out of every `factor`
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for data in iter {
+            self.write(data);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::DecimatingRingBuffer;
+
+    #[test]
+    fn write_every_kth() {
+        let mut ring_buffer: DecimatingRingBuffer<u32, 3> = DecimatingRingBuffer::new(2);
+
+        assert_eq!(ring_buffer.write(0), None);
+        assert_eq!(ring_buffer.write(1), Some(1));
+        ring_buffer.extend(2..8);
+        assert!(ring_buffer.iter().eq(&[2, 4, 6]));
+        assert_eq!(ring_buffer.write(8), Some(2));
+
+        ring_buffer.write(9);
+        ring_buffer.clear();
+        assert_eq!(ring_buffer.write(10), None);
+        assert_eq!(ring_buffer.read(), Some(10));
+    }
+
+    #[test]
+    fn write_factor_one_stores_everything() {
+        let mut ring_buffer: DecimatingRingBuffer<u32, 4> = DecimatingRingBuffer::new(1);
+
+        ring_buffer.extend(0..3);
+        assert!(ring_buffer.iter().eq(&[0, 1, 2]));
+    }
+
+    #[test]
+    #[should_panic(expected = "decimation factor must not be zero")]
+    fn new_zero_factor() {
+        let _ring_buffer: DecimatingRingBuffer<u32, 4> = DecimatingRingBuffer::new(0);
+    }
+}
@@ -0,0 +1,174 @@
+//! A ring buffer that skips writes repeating its newest element
+use core::mem;
+use core::ops::Deref;
+
+use crate::RingBuffer;
+use crate::policy::{OverflowPolicy, Overwrite};
+
+/// What [`DedupRingBuffer::write`] did with the value written
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DedupWrite<T> {
+    /// The value was stored and nothing was displaced
+    Stored,
+    /// The value equals the newest element, so it was not stored and is handed back
+    Duplicate(T),
+    /// The ring buffer was full, and this element did not make it into it under the overflow
+    /// policy: the oldest one by default, or the value itself with
+    /// [`Reject`](crate::policy::Reject)
+    Displaced(T),
+}
+
+/// A FIFO ring buffer that drops a write when it equals the newest stored element, counting
+/// how many were suppressed
+///
+/// Only the newest element still in the ring buffer is compared, so a value repeating one that
+/// has already been read is stored again
+#[derive(Clone, Debug)]
+pub struct DedupRingBuffer<T, const N: usize, P = Overwrite> {
+    ring_buffer: RingBuffer<T, N, P>,
+    suppressed: u64,
+}
+
+impl<T, const N: usize, P> DedupRingBuffer<T, N, P> {
+    /// Creates a new, empty DedupRingBuffer instance
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let ring_buffer: sringbuf::DedupRingBuffer<u8, 5> = sringbuf::DedupRingBuffer::new();
+    /// assert_eq!(ring_buffer.suppressed(), 0);
+    /// ```
+    pub const fn new() -> DedupRingBuffer<T, N, P> {
+        DedupRingBuffer {
+            ring_buffer: RingBuffer::new(),
+            suppressed: 0,
+        }
+    }
+
+    /// Writes data to a ring buffer unless it equals the newest element
+    ///
+    /// A duplicate is handed back in [`DedupWrite::Duplicate`] and counted in
+    /// [`suppressed`](DedupRingBuffer::suppressed). Otherwise the overflow policy `P` applies as
+    /// for [`GenericRingBuffer::write`](crate::GenericRingBuffer::write), and the element that did
+    /// not make it into the ring buffer is returned in [`DedupWrite::Displaced`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sringbuf::DedupWrite;
+    ///
+    /// let mut ring_buffer: sringbuf::DedupRingBuffer<u8, 2> = sringbuf::DedupRingBuffer::new();
+    /// ring_buffer.extend([20, 20, 20]);
+    /// assert_eq!(ring_buffer.write(21), DedupWrite::Stored);
+    /// assert_eq!(ring_buffer.write(21), DedupWrite::Duplicate(21));
+    /// assert_eq!(ring_buffer.write(20), DedupWrite::Displaced(20));
+    /// assert!(ring_buffer.iter().eq(&[21, 20]));
+    /// assert_eq!(ring_buffer.suppressed(), 3);
+    /// ```
+    pub fn write(&mut self, data: T) -> DedupWrite<T>
+    where
+        T: PartialEq,
+        P: OverflowPolicy,
+    {
+        if self.ring_buffer.last() == Some(&data) {
+            self.suppressed += 1;
+            return DedupWrite::Duplicate(data);
+        }
+
+        match self.ring_buffer.write(data) {
+            Some(displaced) => DedupWrite::Displaced(displaced),
+            None => DedupWrite::Stored,
+        }
+    }
+
+    /// Reads the oldest element from a ring buffer, or returns `None` if it is empty
+    pub fn read(&mut self) -> Option<T> {
+        self.ring_buffer.read()
+    }
+
+    /// Returns the number of duplicate writes dropped so far
+    pub fn suppressed(&self) -> u64 {
+        self.suppressed
+    }
+
+    /// Resets the count of duplicate writes to zero, returning the previous count
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::DedupRingBuffer<u8, 4> = sringbuf::DedupRingBuffer::new();
+    /// ring_buffer.extend([1, 1]);
+    /// assert_eq!(ring_buffer.take_suppressed(), 1);
+    /// assert_eq!(ring_buffer.suppressed(), 0);
+    /// ```
+    pub fn take_suppressed(&mut self) -> u64 {
+        mem::take(&mut self.suppressed)
+    }
+
+    /// Drops every element of a ring buffer, keeping the count of duplicate writes
+    pub fn clear(&mut self) {
+        self.ring_buffer.clear();
+    }
+
+    /// Returns the underlying ring buffer, dropping the count of duplicate writes
+    pub fn into_inner(self) -> RingBuffer<T, N, P> {
+        self.ring_buffer
+    }
+}
+
+impl<T, const N: usize, P> Deref for DedupRingBuffer<T, N, P> {
+    type Target = RingBuffer<T, N, P>;
+
+    fn deref(&self) -> &RingBuffer<T, N, P> {
+        &self.ring_buffer
+    }
+}
+
+impl<T, const N: usize, P> Default for DedupRingBuffer<T, N, P> {
+    /// Creates an empty ring buffer
+    fn default() -> DedupRingBuffer<T, N, P> {
+        DedupRingBuffer::new()
+    }
+}
+
+impl<T: PartialEq, const N: usize, P: OverflowPolicy> Extend<T> for DedupRingBuffer<T, N, P> {
+    /// Writes every element of an iterator to a ring buffer, skipping repeats
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for data in iter {
+            self.write(data);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::policy::Reject;
+    use crate::{DedupRingBuffer, DedupWrite};
+
+    #[test]
+    fn write_compares_newest_only() {
+        let mut ring_buffer: DedupRingBuffer<u8, 3> = DedupRingBuffer::new();
+
+        ring_buffer.extend([1, 1, 2, 1, 1]);
+        assert!(ring_buffer.iter().eq(&[1, 2, 1]));
+        assert_eq!(ring_buffer.suppressed(), 2);
+
+        ring_buffer.clear();
+        assert_eq!(ring_buffer.write(1), DedupWrite::Stored);
+        assert_eq!(ring_buffer.read(), Some(1));
+        assert_eq!(ring_buffer.write(1), DedupWrite::Stored);
+        assert_eq!(ring_buffer.len(), 1);
+        assert_eq!(ring_buffer.suppressed(), 2);
+    }
+
+    #[test]
+    fn write_reject_policy() {
+        let mut ring_buffer: DedupRingBuffer<u8, 2, Reject> = DedupRingBuffer::new();
+
+        ring_buffer.extend([1, 2]);
+        assert_eq!(ring_buffer.write(2), DedupWrite::Duplicate(2));
+        assert_eq!(ring_buffer.write(3), DedupWrite::Displaced(3));
+        assert_eq!(ring_buffer.suppressed(), 1);
+        assert!(ring_buffer.iter().eq(&[1, 2]));
+    }
+}
@@ -0,0 +1,96 @@
+//! Conversions between ring buffers and [`VecDeque`]
+use alloc::collections::VecDeque;
+use core::mem::MaybeUninit;
+
+use crate::{Cursor, GenericRingBuffer, RingBuffer, Storage};
+
+impl<T, S, P, C> From<GenericRingBuffer<T, S, P, C>> for VecDeque<T>
+where
+    S: Storage<T>,
+    C: Cursor,
+{
+    /// Moves the elements of a ring buffer into a deque, the oldest at the front
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::VecDeque;
+    ///
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 3> = sringbuf::RingBuffer::new();
+    /// ring_buffer.extend([1, 2, 3, 4]);
+    ///
+    /// let deque = VecDeque::from(ring_buffer);
+    /// assert_eq!(deque, [2, 3, 4]);
+    /// ```
+    fn from(ring_buffer: GenericRingBuffer<T, S, P, C>) -> VecDeque<T> {
+        let mut deque = VecDeque::with_capacity(ring_buffer.len());
+        deque.extend(ring_buffer);
+        deque
+    }
+}
+
+impl<T, const N: usize, P, C: Cursor> TryFrom<VecDeque<T>> for RingBuffer<T, N, P, C> {
+    type Error = VecDeque<T>;
+
+    /// Moves the elements of a deque into a ring buffer, the front one being the oldest, or
+    /// hands the deque back if it holds more than `N` elements
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::VecDeque;
+    ///
+    /// let deque = VecDeque::from([1, 2]);
+    /// let mut ring_buffer = sringbuf::RingBuffer::<u8, 3>::try_from(deque).unwrap();
+    /// assert_eq!(ring_buffer.read(), Some(1));
+    ///
+    /// let deque = VecDeque::from([1, 2, 3, 4]);
+    /// assert!(sringbuf::RingBuffer::<u8, 3>::try_from(deque).is_err());
+    /// ```
+    fn try_from(deque: VecDeque<T>) -> Result<RingBuffer<T, N, P, C>, VecDeque<T>> {
+        if deque.len() > N {
+            return Err(deque);
+        }
+
+        let mut ring_buffer = RingBuffer::new();
+        for (slot, data) in deque.into_iter().enumerate() {
+            ring_buffer.slots_mut()[slot] = MaybeUninit::new(data);
+            ring_buffer.set_len(slot + 1);
+        }
+        Ok(ring_buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::rc::Rc;
+
+    use crate::{HeapRingBuffer, RingBuffer};
+
+    #[test]
+    fn vec_deque_from_wrapped() {
+        let mut ring_buffer: HeapRingBuffer<String> = HeapRingBuffer::with_capacity(2);
+        ring_buffer.extend(["a", "b", "c"].map(String::from));
+
+        let deque = VecDeque::from(ring_buffer);
+        assert_eq!(deque, ["b", "c"]);
+    }
+
+    #[test]
+    fn try_from_vec_deque_round_trip() {
+        let counter = Rc::new(());
+        let mut deque = VecDeque::new();
+        deque.push_back(Rc::clone(&counter));
+        deque.push_front(Rc::new(()));
+
+        let ring_buffer = RingBuffer::<Rc<()>, 2>::try_from(deque).unwrap();
+        assert!(ring_buffer.is_full());
+        assert_eq!(Rc::strong_count(ring_buffer.peek().unwrap()), 1);
+
+        let deque = VecDeque::from(ring_buffer);
+        assert_eq!(Rc::strong_count(&counter), 2);
+        let error = RingBuffer::<Rc<()>, 1>::try_from(deque).unwrap_err();
+        assert_eq!(error.len(), 2);
+    }
+}
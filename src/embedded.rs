@@ -0,0 +1,230 @@
+//! [`embedded_io`] trait implementations for byte ring buffers and the SPSC halves
+#[cfg(target_has_atomic = "ptr")]
+use core::convert::Infallible;
+#[cfg(target_has_atomic = "ptr")]
+use core::hint;
+
+use embedded_io::{BufRead, ErrorKind, ErrorType, Read, ReadReady, Write, WriteReady};
+
+#[cfg(target_has_atomic = "ptr")]
+use crate::spsc::{Consumer, Producer};
+use crate::{Cursor, GenericRingBuffer, Storage};
+
+impl<S, P, C> ErrorType for GenericRingBuffer<u8, S, P, C>
+where
+    S: Storage<u8>,
+    C: Cursor,
+{
+    type Error = ErrorKind;
+}
+
+impl<S, P, C> Read for GenericRingBuffer<u8, S, P, C>
+where
+    S: Storage<u8>,
+    C: Cursor,
+{
+    /// Reads the oldest bytes that fit in `buf`; an empty ring buffer reads as the end of the
+    /// data, since nothing else can write to it while it is borrowed
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, ErrorKind> {
+        Ok(self.read_into(buf))
+    }
+}
+
+impl<S, P, C> BufRead for GenericRingBuffer<u8, S, P, C>
+where
+    S: Storage<u8>,
+    C: Cursor,
+{
+    /// Returns the oldest contiguous run of bytes, which ends at the wrap point if the
+    /// contents wrap around
+    fn fill_buf(&mut self) -> Result<&[u8], ErrorKind> {
+        Ok(self.as_slices().0)
+    }
+
+    /// Discards the oldest `amt` bytes, or every byte if there are fewer
+    fn consume(&mut self, amt: usize) {
+        let amt = amt.min(self.len());
+        self.set_read_index(self.slot_index(amt));
+        self.set_len(self.len() - amt);
+    }
+}
+
+impl<S, P, C> Write for GenericRingBuffer<u8, S, P, C>
+where
+    S: Storage<u8>,
+    C: Cursor,
+{
+    /// Writes as many bytes as fit in the free space, failing with [`ErrorKind::WriteZero`]
+    /// rather than overwriting unread data when the ring buffer is full
+    fn write(&mut self, buf: &[u8]) -> Result<usize, ErrorKind> {
+        match self.write_from(buf) {
+            0 if !buf.is_empty() => Err(ErrorKind::WriteZero),
+            count => Ok(count),
+        }
+    }
+
+    fn flush(&mut self) -> Result<(), ErrorKind> {
+        Ok(())
+    }
+}
+
+impl<S, P, C> ReadReady for GenericRingBuffer<u8, S, P, C>
+where
+    S: Storage<u8>,
+    C: Cursor,
+{
+    /// Reads never block, so a ring buffer is always ready
+    fn read_ready(&mut self) -> Result<bool, ErrorKind> {
+        Ok(true)
+    }
+}
+
+impl<S, P, C> WriteReady for GenericRingBuffer<u8, S, P, C>
+where
+    S: Storage<u8>,
+    C: Cursor,
+{
+    /// Writes never block, so a ring buffer is always ready
+    fn write_ready(&mut self) -> Result<bool, ErrorKind> {
+        Ok(true)
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<const N: usize> ErrorType for Consumer<'_, u8, N> {
+    type Error = Infallible;
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<const N: usize> Read for Consumer<'_, u8, N> {
+    /// Spins until bytes arrive, reading the end of the data once the producer is closed and
+    /// everything queued has been read
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Infallible> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        while self.is_empty() && !self.is_closed() {
+            hint::spin_loop();
+        }
+        Ok(self.read_into(buf))
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<const N: usize> ReadReady for Consumer<'_, u8, N> {
+    /// Returns `true` once a read would return bytes or the end of the data without spinning
+    fn read_ready(&mut self) -> Result<bool, Infallible> {
+        Ok(!self.is_empty() || self.is_closed())
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<const N: usize> ErrorType for Producer<'_, u8, N> {
+    type Error = ErrorKind;
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<const N: usize> Write for Producer<'_, u8, N> {
+    /// Spins until there is free space and writes as many bytes as fit, failing with
+    /// [`ErrorKind::BrokenPipe`] once the ring buffer is closed
+    fn write(&mut self, buf: &[u8]) -> Result<usize, ErrorKind> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        while self.is_full() && !self.is_closed() {
+            hint::spin_loop();
+        }
+        if self.is_closed() {
+            return Err(ErrorKind::BrokenPipe);
+        }
+        Ok(self.write_from(buf))
+    }
+
+    fn flush(&mut self) -> Result<(), ErrorKind> {
+        Ok(())
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<const N: usize> WriteReady for Producer<'_, u8, N> {
+    /// Returns `true` once a write would succeed or fail without spinning
+    fn write_ready(&mut self) -> Result<bool, ErrorKind> {
+        Ok(!self.is_full() || self.is_closed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RingBuffer;
+    use crate::spsc::Queue;
+
+    use embedded_io::{BufRead, ErrorKind, Read, ReadReady, Write, WriteReady};
+    use std::thread;
+
+    #[test]
+    fn ring_buffer_round_trip() {
+        let mut ring_buffer: RingBuffer<u8, 4> = RingBuffer::new();
+
+        assert_eq!(Write::write(&mut ring_buffer, b"abcdef"), Ok(4));
+        assert_eq!(
+            Write::write(&mut ring_buffer, b"ef"),
+            Err(ErrorKind::WriteZero)
+        );
+        assert_eq!(Write::write(&mut ring_buffer, b""), Ok(0));
+
+        let mut dst = [0; 3];
+        assert_eq!(Read::read(&mut ring_buffer, &mut dst), Ok(3));
+        assert_eq!(&dst, b"abc");
+        ring_buffer.write_all(b"ef").unwrap();
+        assert_eq!(ring_buffer.fill_buf(), Ok(&b"d"[..]));
+        ring_buffer.consume(1);
+        assert_eq!(ring_buffer.fill_buf(), Ok(&b"ef"[..]));
+    }
+
+    #[test]
+    fn split_pipe_copies_everything() {
+        const COUNT: usize = if cfg!(miri) { 100 } else { 1_000 };
+
+        let mut queue: Queue<u8, 7> = Queue::new();
+        let (mut producer, mut consumer) = queue.split();
+        let data: Vec<u8> = (0..COUNT).map(|i| i as u8).collect();
+
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                producer.write_all(&data).unwrap();
+                drop(producer);
+            });
+
+            let mut received = Vec::new();
+            let mut chunk = [0; 5];
+            loop {
+                match Read::read(&mut consumer, &mut chunk).unwrap() {
+                    0 => break,
+                    count => received.extend_from_slice(&chunk[..count]),
+                }
+            }
+            assert_eq!(received, data);
+        });
+    }
+
+    #[test]
+    fn split_ready_and_closed() {
+        let mut queue: Queue<u8, 1> = Queue::new();
+        let (mut producer, mut consumer) = queue.split();
+
+        assert_eq!(consumer.read_ready(), Ok(false));
+        assert_eq!(producer.write_ready(), Ok(true));
+        producer.write_all(b"a").unwrap();
+        assert_eq!(consumer.read_ready(), Ok(true));
+        assert_eq!(producer.write_ready(), Ok(false));
+
+        drop(consumer);
+        assert_eq!(producer.write_ready(), Ok(true));
+        assert_eq!(
+            Write::write(&mut producer, b"b"),
+            Err(ErrorKind::BrokenPipe)
+        );
+    }
+}
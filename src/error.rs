@@ -0,0 +1,140 @@
+//! Error types returned by fallible ring buffer operations
+use core::fmt;
+
+/// The error returned when writing to a ring buffer that has no free space
+///
+/// The rejected value is handed back so that it is not lost
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Full<T>(pub T);
+
+impl<T> Full<T> {
+    /// Returns the value that could not be written
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 1> = sringbuf::RingBuffer::new();
+    /// ring_buffer.write(1);
+    /// let error = ring_buffer.try_write(2).unwrap_err();
+    /// assert_eq!(error.into_inner(), 2);
+    /// ```
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> fmt::Debug for Full<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Full").finish_non_exhaustive()
+    }
+}
+
+impl<T> fmt::Display for Full<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ring buffer is full")
+    }
+}
+
+impl<T> core::error::Error for Full<T> {}
+
+/// The error returned when building a ring buffer from more elements than its capacity
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CapacityError {
+    /// The number of elements given
+    pub len: usize,
+    /// The capacity of the ring buffer
+    pub capacity: usize,
+}
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} elements do not fit in a ring buffer of capacity {}",
+            self.len, self.capacity
+        )
+    }
+}
+
+impl core::error::Error for CapacityError {}
+
+/// The error returned when a blocking operation gives up after its timeout
+///
+/// A timed-out write hands its value back in the error; a timed-out read holds `()`
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Timeout<T = ()>(pub T);
+
+impl<T> Timeout<T> {
+    /// Returns the value that could not be written
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let error = sringbuf::Timeout(2);
+    /// assert_eq!(error.into_inner(), 2);
+    /// ```
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> fmt::Debug for Timeout<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Timeout").finish_non_exhaustive()
+    }
+}
+
+impl<T> fmt::Display for Timeout<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("timed out waiting on the ring buffer")
+    }
+}
+
+impl<T> core::error::Error for Timeout<T> {}
+
+/// The error returned when a reader missed elements that were overwritten before it read them
+///
+/// It holds the number of elements skipped; the next read resumes at the oldest element left
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Lagged(pub u64);
+
+impl fmt::Display for Lagged {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "reader lagged behind by {} elements", self.0)
+    }
+}
+
+impl core::error::Error for Lagged {}
+
+/// The error returned when a ring buffer snapshot cannot be written or restored
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SnapshotError {
+    /// The destination is too short for the snapshot
+    BufferTooSmall,
+    /// The snapshot ends before the elements it announces
+    Truncated,
+    /// The snapshot was written in a format version this crate does not read
+    UnsupportedVersion(u8),
+    /// The snapshot was written for elements of a different encoded size
+    ElementSize,
+    /// The snapshot holds more elements than the ring buffer can
+    TooManyElements,
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::BufferTooSmall => f.write_str("buffer is too small for the snapshot"),
+            SnapshotError::Truncated => f.write_str("snapshot is truncated"),
+            SnapshotError::UnsupportedVersion(version) => {
+                write!(f, "unsupported snapshot version {version}")
+            }
+            SnapshotError::ElementSize => f.write_str("snapshot element size does not match"),
+            SnapshotError::TooManyElements => {
+                f.write_str("snapshot holds more elements than the ring buffer")
+            }
+        }
+    }
+}
+
+impl core::error::Error for SnapshotError {}
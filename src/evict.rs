@@ -0,0 +1,109 @@
+//! A ring buffer that hands every overwritten element to a callback
+use core::ops::Deref;
+
+use crate::RingBuffer;
+
+/// A FIFO ring buffer that overwrites its oldest element when full, passing that element to an
+/// `on_evict` callback instead of dropping it
+///
+/// The callback can log evicted data or spill it to slower storage. Only elements displaced by
+/// a write reach it: elements that are read or cleared do not
+#[derive(Clone, Debug)]
+pub struct EvictingRingBuffer<T, const N: usize, F> {
+    ring_buffer: RingBuffer<T, N>,
+    on_evict: F,
+}
+
+impl<T, const N: usize, F: FnMut(T)> EvictingRingBuffer<T, N, F> {
+    /// Creates a new, empty EvictingRingBuffer instance calling `on_evict` with every element
+    /// it overwrites
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let ring_buffer: sringbuf::EvictingRingBuffer<u8, 4, _> =
+    ///     sringbuf::EvictingRingBuffer::new(|evicted| println!("lost {evicted}"));
+    /// assert!(ring_buffer.is_empty());
+    /// ```
+    pub const fn new(on_evict: F) -> EvictingRingBuffer<T, N, F> {
+        EvictingRingBuffer {
+            ring_buffer: RingBuffer::new(),
+            on_evict,
+        }
+    }
+
+    /// Writes data to a ring buffer, first passing the oldest element to the callback if it is
+    /// full
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut spilled = Vec::new();
+    /// let mut ring_buffer: sringbuf::EvictingRingBuffer<u8, 2, _> =
+    ///     sringbuf::EvictingRingBuffer::new(|evicted| spilled.push(evicted));
+    /// ring_buffer.extend([1, 2, 3, 4]);
+    /// assert!(ring_buffer.iter().eq(&[3, 4]));
+    ///
+    /// drop(ring_buffer);
+    /// assert_eq!(spilled, [1, 2]);
+    /// ```
+    pub fn write(&mut self, data: T) {
+        if let Some(evicted) = self.ring_buffer.write(data) {
+            (self.on_evict)(evicted);
+        }
+    }
+
+    /// Reads the oldest element from a ring buffer, or returns `None` if it is empty
+    pub fn read(&mut self) -> Option<T> {
+        self.ring_buffer.read()
+    }
+
+    /// Drops every element of a ring buffer without passing them to the callback
+    pub fn clear(&mut self) {
+        self.ring_buffer.clear();
+    }
+
+    /// Returns the underlying ring buffer and the callback
+    pub fn into_parts(self) -> (RingBuffer<T, N>, F) {
+        (self.ring_buffer, self.on_evict)
+    }
+}
+
+impl<T, const N: usize, F> Deref for EvictingRingBuffer<T, N, F> {
+    type Target = RingBuffer<T, N>;
+
+    fn deref(&self) -> &RingBuffer<T, N> {
+        &self.ring_buffer
+    }
+}
+
+impl<T, const N: usize, F: FnMut(T)> Extend<T> for EvictingRingBuffer<T, N, F> {
+    /// Writes every element of an iterator to a ring buffer
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for data in iter {
+            self.write(data);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::EvictingRingBuffer;
+
+    #[test]
+    fn write_evicts_only_unread() {
+        let mut evicted = Vec::new();
+        let mut ring_buffer: EvictingRingBuffer<u32, 3, _> =
+            EvictingRingBuffer::new(|data| evicted.push(data));
+
+        ring_buffer.extend(0..3);
+        assert_eq!(ring_buffer.read(), Some(0));
+        ring_buffer.extend(3..6);
+        ring_buffer.clear();
+        ring_buffer.write(6);
+
+        let (ring_buffer, _) = ring_buffer.into_parts();
+        assert!(ring_buffer.iter().eq(&[6]));
+        assert_eq!(evicted, [1, 2]);
+    }
+}
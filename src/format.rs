@@ -0,0 +1,45 @@
+//! [`defmt::Format`] implementation for logging ring buffers on embedded targets
+use defmt::{Format, Formatter};
+
+use crate::{Cursor, GenericRingBuffer, Storage};
+
+impl<T, S, P, C> Format for GenericRingBuffer<T, S, P, C>
+where
+    T: Format,
+    S: Storage<T>,
+    C: Cursor,
+{
+    /// Logs the occupancy and the elements from oldest to newest, leaving out the slot layout
+    fn format(&self, f: Formatter<'_>) {
+        defmt::write!(
+            f,
+            "{=str} {{ len: {=usize}, capacity: {=usize}, contents: [",
+            S::NAME,
+            self.len(),
+            self.capacity()
+        );
+        for (index, data) in self.iter().enumerate() {
+            if index > 0 {
+                defmt::write!(f, ", ");
+            }
+            defmt::write!(f, "{}", data);
+        }
+        defmt::write!(f, "] }}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RingBuffer;
+    use crate::policy::Reject;
+
+    /// Logging needs a global logger linked in, so the tests only check which types implement
+    /// `Format`
+    fn assert_format<T: defmt::Format>() {}
+
+    #[test]
+    fn format_nested_ring_buffers() {
+        assert_format::<RingBuffer<u8, 4>>();
+        assert_format::<RingBuffer<RingBuffer<i32, 2>, 3, Reject>>();
+    }
+}
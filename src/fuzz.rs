@@ -0,0 +1,70 @@
+//! [`arbitrary::Arbitrary`] implementation for fuzzing code that takes ring buffers
+use core::mem::MaybeUninit;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{Cursor, RingBuffer};
+
+impl<'a, T, const N: usize, P, C: Cursor> Arbitrary<'a> for RingBuffer<T, N, P, C>
+where
+    T: Arbitrary<'a>,
+{
+    /// Builds a ring buffer with an arbitrary read cursor and length, so the contents may be
+    /// empty, partly filled, full or wrapped around the end of the array
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<RingBuffer<T, N, P, C>> {
+        let mut ring_buffer = RingBuffer::new();
+        ring_buffer.set_read_index(u.choose_index(N)?);
+
+        let len = u.int_in_range(0..=N)?;
+        for _ in 0..len {
+            let data = T::arbitrary(u)?;
+            let write_index = ring_buffer.write_index();
+            ring_buffer.slots_mut()[write_index] = MaybeUninit::new(data);
+            ring_buffer.set_len(ring_buffer.len() + 1);
+        }
+        Ok(ring_buffer)
+    }
+
+    /// Any input builds a ring buffer, since running out of bytes leaves it empty
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        let (_, element_upper) = T::size_hint(depth);
+        let upper = element_upper
+            .and_then(|upper| upper.checked_mul(N))
+            .and_then(|upper| upper.checked_add(2 * size_of::<usize>()));
+        (0, upper)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RingBuffer;
+
+    use arbitrary::{Arbitrary, Unstructured};
+
+    #[test]
+    fn arbitrary_varied_states() {
+        let (mut empty, mut full, mut wrapped) = (false, false, false);
+
+        for seed in 0..=255u8 {
+            let bytes: Vec<u8> = (0..64)
+                .map(|i| seed.wrapping_mul(31).wrapping_add(i))
+                .collect();
+            let ring_buffer =
+                RingBuffer::<u16, 5>::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+
+            let (head, tail) = ring_buffer.as_slices();
+            assert_eq!(head.len() + tail.len(), ring_buffer.len());
+            empty |= ring_buffer.is_empty();
+            full |= ring_buffer.is_full();
+            wrapped |= !tail.is_empty();
+        }
+        assert!(empty && full && wrapped);
+    }
+
+    #[test]
+    fn arbitrary_exhausted_input() {
+        let ring_buffer = RingBuffer::<String, 3>::arbitrary(&mut Unstructured::new(&[])).unwrap();
+        assert!(ring_buffer.is_empty());
+        assert_eq!(RingBuffer::<String, 3>::size_hint(0).0, 0);
+    }
+}
@@ -0,0 +1,160 @@
+//! Two-phase writes straight into the free slots of a ring buffer
+use core::fmt;
+use core::mem::MaybeUninit;
+
+use crate::policy::Overwrite;
+use crate::{Cursor, GenericRingBuffer, Storage, UninitStorage};
+
+impl<T, S, P, C> GenericRingBuffer<T, S, P, C>
+where
+    S: UninitStorage<T>,
+    C: Cursor,
+{
+    /// Reserves `n` contiguous free slots after the newest element, or returns `None` if the
+    /// free space does not hold `n` slots without wrapping around the end of the storage
+    ///
+    /// The slots can be filled in place, by a driver or a DMA engine for instance, and then
+    /// published with [`WriteGrant::commit`]. Nothing is written until the grant is committed.
+    /// Since the slots may be left uninitialized, a
+    /// [`RingBufferSlice`](crate::RingBufferSlice) offers no grants
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::mem::MaybeUninit;
+    ///
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 8> = sringbuf::RingBuffer::new();
+    /// let mut grant = ring_buffer.grant(4).unwrap();
+    /// grant.buf()[..2].copy_from_slice(&[MaybeUninit::new(1), MaybeUninit::new(2)]);
+    /// // SAFETY: the first two slots of the grant were initialized above
+    /// unsafe { grant.commit(2) };
+    /// assert!(ring_buffer.iter().eq(&[1, 2]));
+    /// assert!(ring_buffer.grant(7).is_none());
+    /// ```
+    ///
+    /// ```compile_fail
+    /// let mut memory = [0u8; 4];
+    /// let mut ring_buffer: sringbuf::RingBufferSlice<u8> =
+    ///     sringbuf::RingBufferSlice::from_slice(&mut memory);
+    /// ring_buffer.grant(1);
+    /// ```
+    pub fn grant(&mut self, n: usize) -> Option<WriteGrant<'_, T, S, P, C>> {
+        let (head, _) = self.free_slots_mut();
+        if n > head.len() {
+            return None;
+        }
+
+        Some(WriteGrant {
+            write_index: self.write_index(),
+            len: n,
+            ring_buffer: self,
+        })
+    }
+}
+
+/// A run of free slots reserved for writing in place
+///
+/// This struct is created by [`RingBuffer::grant`](crate::RingBuffer::grant). Dropping it
+/// without calling [`commit`](WriteGrant::commit) leaves the ring buffer as it was
+pub struct WriteGrant<'a, T, S, P = Overwrite, C = usize>
+where
+    S: Storage<T>,
+    C: Cursor,
+{
+    ring_buffer: &'a mut GenericRingBuffer<T, S, P, C>,
+    write_index: usize,
+    len: usize,
+}
+
+impl<T, S, P, C> WriteGrant<'_, T, S, P, C>
+where
+    S: Storage<T>,
+    C: Cursor,
+{
+    /// Returns the reserved slots, oldest first
+    pub fn buf(&mut self) -> &mut [MaybeUninit<T>] {
+        &mut self.ring_buffer.slots_mut()[self.write_index..self.write_index + self.len]
+    }
+
+    /// Returns the number of reserved slots
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no slots are reserved
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Publishes the first `len` reserved slots as the newest elements of the ring buffer
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is more than the number of reserved slots
+    ///
+    /// # Safety
+    ///
+    /// The first `len` slots of [`buf`](WriteGrant::buf) must have been initialized
+    pub unsafe fn commit(self, len: usize) {
+        assert!(len <= self.len, "commit exceeds the granted slots");
+
+        self.ring_buffer.set_len(self.ring_buffer.len() + len);
+    }
+}
+
+impl<T, S, P, C> fmt::Debug for WriteGrant<'_, T, S, P, C>
+where
+    S: Storage<T>,
+    C: Cursor,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WriteGrant")
+            .field("write_index", &self.write_index)
+            .field("len", &self.len)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RingBuffer;
+
+    use core::mem::MaybeUninit;
+
+    #[test]
+    fn grant_contiguous_only() {
+        let mut ring_buffer: RingBuffer<u8, 4> = RingBuffer::new();
+        ring_buffer.extend([1, 2, 3]);
+        ring_buffer.advance_read(2);
+
+        assert!(ring_buffer.grant(2).is_none());
+        let mut grant = ring_buffer.grant(1).unwrap();
+        assert_eq!(grant.len(), 1);
+        grant.buf()[0] = MaybeUninit::new(4);
+        // SAFETY: the only granted slot was initialized above
+        unsafe { grant.commit(1) };
+
+        ring_buffer.grant(2).unwrap().buf()[0] = MaybeUninit::new(5);
+        assert!(ring_buffer.iter().eq(&[3, 4]));
+
+        let mut grant = ring_buffer.grant(2).unwrap();
+        grant
+            .buf()
+            .copy_from_slice(&[MaybeUninit::new(5), MaybeUninit::new(6)]);
+        // SAFETY: both granted slots were initialized above
+        unsafe { grant.commit(2) };
+        assert!(ring_buffer.iter().eq(&[3, 4, 5, 6]));
+        assert!(ring_buffer.is_full());
+        assert!(ring_buffer.grant(1).is_none());
+        assert!(ring_buffer.grant(0).unwrap().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "commit exceeds the granted slots")]
+    fn grant_commit_too_long() {
+        let mut ring_buffer: RingBuffer<u8, 4> = RingBuffer::new();
+        let grant = ring_buffer.grant(1).unwrap();
+        // SAFETY: the commit panics before publishing anything
+        unsafe { grant.commit(2) };
+    }
+}
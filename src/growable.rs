@@ -0,0 +1,258 @@
+//! A ring buffer that grows instead of overflowing
+use alloc::boxed::Box;
+use core::mem::MaybeUninit;
+use core::ops::Deref;
+
+use crate::policy::{OverflowPolicy, Overwrite};
+use crate::{Drain, HeapRingBuffer};
+
+/// A FIFO ring buffer on the heap that doubles its capacity when a write finds it full
+///
+/// Growth stops at an optional maximum capacity, after which writes follow the overflow policy
+/// `P` like any other ring buffer
+#[derive(Clone, Debug, PartialEq)]
+pub struct GrowableRingBuffer<T, P = Overwrite> {
+    ring_buffer: HeapRingBuffer<T, P>,
+    max_capacity: Option<usize>,
+}
+
+impl<T, P> GrowableRingBuffer<T, P> {
+    /// Creates a new GrowableRingBuffer instance with room for `capacity` elements and no limit
+    /// on growth
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::GrowableRingBuffer<u8> = sringbuf::GrowableRingBuffer::with_capacity(2);
+    /// ring_buffer.write(1);
+    /// ring_buffer.write(2);
+    /// ring_buffer.write(3);
+    /// assert_eq!(ring_buffer.capacity(), 4);
+    /// assert_eq!(ring_buffer.read(), Some(1));
+    /// ```
+    pub fn with_capacity(capacity: usize) -> GrowableRingBuffer<T, P> {
+        GrowableRingBuffer {
+            ring_buffer: HeapRingBuffer::with_capacity(capacity),
+            max_capacity: None,
+        }
+    }
+
+    /// Creates a new GrowableRingBuffer instance with room for `capacity` elements that grows up
+    /// to `max_capacity` elements
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero or greater than `max_capacity`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::GrowableRingBuffer<u8> = sringbuf::GrowableRingBuffer::with_max_capacity(2, 3);
+    /// ring_buffer.extend([1, 2, 3, 4]);
+    /// assert_eq!(ring_buffer.capacity(), 3);
+    /// assert_eq!(ring_buffer.read(), Some(2));
+    /// ```
+    pub fn with_max_capacity(capacity: usize, max_capacity: usize) -> GrowableRingBuffer<T, P> {
+        assert!(capacity <= max_capacity);
+
+        GrowableRingBuffer {
+            ring_buffer: HeapRingBuffer::with_capacity(capacity),
+            max_capacity: Some(max_capacity),
+        }
+    }
+
+    /// Returns the capacity a ring buffer may grow to, or `None` if growth is unbounded
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let ring_buffer: sringbuf::GrowableRingBuffer<u8> = sringbuf::GrowableRingBuffer::with_max_capacity(2, 8);
+    /// assert_eq!(ring_buffer.max_capacity(), Some(8));
+    /// ```
+    pub fn max_capacity(&self) -> Option<usize> {
+        self.max_capacity
+    }
+
+    /// Writes data to a ring buffer, doubling its capacity first if it is full
+    ///
+    /// Once the maximum capacity is reached, the write follows the overflow policy `P` and the
+    /// return value is the same as [`GenericRingBuffer::write`](crate::GenericRingBuffer::write)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::GrowableRingBuffer<u8> = sringbuf::GrowableRingBuffer::with_capacity(1);
+    /// assert_eq!(ring_buffer.write(1), None);
+    /// assert_eq!(ring_buffer.write(2), None);
+    /// assert_eq!(ring_buffer.len(), 2);
+    /// ```
+    pub fn write(&mut self, data: T) -> Option<T>
+    where
+        P: OverflowPolicy,
+    {
+        if self.ring_buffer.is_full() {
+            self.grow();
+        }
+
+        self.ring_buffer.write(data)
+    }
+
+    /// Reads the oldest element from a ring buffer, or returns `None` if it is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::GrowableRingBuffer<u8> = sringbuf::GrowableRingBuffer::with_capacity(1);
+    /// ring_buffer.write(1);
+    /// assert_eq!(ring_buffer.read(), Some(1));
+    /// assert_eq!(ring_buffer.read(), None);
+    /// ```
+    pub fn read(&mut self) -> Option<T> {
+        self.ring_buffer.read()
+    }
+
+    /// Drops every element of a ring buffer, keeping its current capacity
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::GrowableRingBuffer<u8> = sringbuf::GrowableRingBuffer::with_capacity(1);
+    /// ring_buffer.extend([1, 2, 3]);
+    /// ring_buffer.clear();
+    /// assert!(ring_buffer.is_empty());
+    /// assert_eq!(ring_buffer.capacity(), 4);
+    /// ```
+    pub fn clear(&mut self) {
+        self.ring_buffer.clear();
+    }
+
+    /// Removes every element of a ring buffer, returning them from oldest to newest
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::GrowableRingBuffer<u8> = sringbuf::GrowableRingBuffer::with_capacity(1);
+    /// ring_buffer.extend([1, 2, 3]);
+    /// assert_eq!(ring_buffer.drain().collect::<Vec<_>>(), [1, 2, 3]);
+    /// assert!(ring_buffer.is_empty());
+    /// ```
+    pub fn drain(&mut self) -> Drain<'_, T, Box<[MaybeUninit<T>]>, P> {
+        self.ring_buffer.drain()
+    }
+
+    /// Returns the underlying ring buffer, which no longer grows
+    pub fn into_inner(self) -> HeapRingBuffer<T, P> {
+        self.ring_buffer
+    }
+
+    /// Doubles the capacity of a ring buffer, clamped to its maximum capacity
+    fn grow(&mut self) {
+        let capacity = self.ring_buffer.capacity();
+        let new_capacity = capacity
+            .saturating_mul(2)
+            .min(self.max_capacity.unwrap_or(usize::MAX));
+        if new_capacity > capacity {
+            self.ring_buffer.reallocate(new_capacity);
+        }
+    }
+}
+
+impl<T, P> Deref for GrowableRingBuffer<T, P> {
+    type Target = HeapRingBuffer<T, P>;
+
+    fn deref(&self) -> &HeapRingBuffer<T, P> {
+        &self.ring_buffer
+    }
+}
+
+impl<T, P> Extend<T> for GrowableRingBuffer<T, P>
+where
+    P: OverflowPolicy,
+{
+    /// Writes every element of an iterator to a ring buffer, growing it as needed
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for data in iter {
+            self.write(data);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::GrowableRingBuffer;
+    use crate::policy::Reject;
+
+    use std::rc::Rc;
+
+    #[test]
+    fn write_linearizes_on_growth() {
+        let mut ring_buffer: GrowableRingBuffer<u8> = GrowableRingBuffer::with_capacity(3);
+
+        ring_buffer.extend([1, 2, 3]);
+        assert_eq!(ring_buffer.read(), Some(1));
+        ring_buffer.write(4);
+        assert_eq!(ring_buffer.as_slices(), (&[2, 3][..], &[4][..]));
+
+        ring_buffer.write(5);
+        assert_eq!(ring_buffer.capacity(), 6);
+        assert_eq!(ring_buffer.as_slices(), (&[2, 3, 4, 5][..], &[][..]));
+    }
+
+    #[test]
+    fn write_unbounded_growth() {
+        let mut ring_buffer: GrowableRingBuffer<u32> = GrowableRingBuffer::with_capacity(1);
+
+        ring_buffer.extend(0..100);
+        assert_eq!(ring_buffer.len(), 100);
+        assert_eq!(ring_buffer.capacity(), 128);
+        assert!(ring_buffer.iter().copied().eq(0..100));
+    }
+
+    #[test]
+    fn write_overwrites_at_max_capacity() {
+        let mut ring_buffer: GrowableRingBuffer<u8> = GrowableRingBuffer::with_max_capacity(2, 5);
+
+        ring_buffer.extend([1, 2, 3, 4, 5]);
+        assert_eq!(ring_buffer.capacity(), 5);
+        assert_eq!(ring_buffer.write(6), Some(1));
+        assert_eq!(
+            ring_buffer.iter().copied().collect::<Vec<_>>(),
+            [2, 3, 4, 5, 6]
+        );
+    }
+
+    #[test]
+    fn write_rejects_at_max_capacity() {
+        let mut ring_buffer: GrowableRingBuffer<u8, Reject> =
+            GrowableRingBuffer::with_max_capacity(1, 2);
+
+        assert_eq!(ring_buffer.write(1), None);
+        assert_eq!(ring_buffer.write(2), None);
+        assert_eq!(ring_buffer.write(3), Some(3));
+        assert_eq!(ring_buffer.iter().copied().collect::<Vec<_>>(), [1, 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn with_max_capacity_below_capacity() {
+        let _ring_buffer: GrowableRingBuffer<u8> = GrowableRingBuffer::with_max_capacity(4, 2);
+    }
+
+    #[test]
+    fn growth_does_not_drop_elements() {
+        let counter = Rc::new(());
+        let mut ring_buffer: GrowableRingBuffer<Rc<()>> = GrowableRingBuffer::with_capacity(1);
+
+        for _ in 0..10 {
+            ring_buffer.write(Rc::clone(&counter));
+        }
+        assert_eq!(Rc::strong_count(&counter), 11);
+
+        drop(ring_buffer);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+}
@@ -0,0 +1,289 @@
+//! A ring buffer whose capacity is chosen at runtime
+use alloc::boxed::Box;
+use core::mem::MaybeUninit;
+use core::ptr;
+
+use crate::{Cursor, GenericRingBuffer};
+use crate::policy::Overwrite;
+
+/// A FIFO ring buffer whose storage is allocated on the heap with a capacity chosen at runtime
+///
+/// It has the same methods as [`RingBuffer`](crate::RingBuffer); only construction differs
+pub type HeapRingBuffer<T, P = Overwrite, C = usize> =
+    GenericRingBuffer<T, Box<[MaybeUninit<T>]>, P, C>;
+
+/// Which elements [`HeapRingBuffer::resize`] keeps when the new capacity is below the length
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Retain {
+    /// Keeps the oldest elements and drops the newest ones
+    Oldest,
+    /// Keeps the newest elements and drops the oldest ones, as overwriting writes would
+    Newest,
+}
+
+impl<T, P, C: Cursor> HeapRingBuffer<T, P, C> {
+    /// Creates a new HeapRingBuffer instance that can hold up to `capacity` elements
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero, or more than the cursor type `C` can count
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::HeapRingBuffer<u8> = sringbuf::HeapRingBuffer::with_capacity(3);
+    /// ring_buffer.write(1);
+    /// assert_eq!(ring_buffer.capacity(), 3);
+    /// assert_eq!(ring_buffer.read(), Some(1));
+    /// ```
+    pub fn with_capacity(capacity: usize) -> HeapRingBuffer<T, P, C> {
+        GenericRingBuffer::from_storage(Box::new_uninit_slice(capacity))
+    }
+
+    /// Splits a ring buffer in two at logical position `at`, keeping the older elements and
+    /// returning a new ring buffer of the same capacity with the elements from `at` onward
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at` is more than the length
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::HeapRingBuffer<u8> = sringbuf::HeapRingBuffer::with_capacity(4);
+    /// ring_buffer.extend([1, 2, 3, 4]);
+    /// let newer = ring_buffer.split_off(2);
+    /// assert!(ring_buffer.iter().eq(&[1, 2]));
+    /// assert!(newer.iter().eq(&[3, 4]));
+    /// assert_eq!(newer.capacity(), 4);
+    /// ```
+    pub fn split_off(&mut self, at: usize) -> HeapRingBuffer<T, P, C> {
+        let mut other = HeapRingBuffer::with_capacity(self.capacity());
+        self.split_off_into(at, &mut other);
+        other
+    }
+
+    /// Changes the capacity of a ring buffer to `capacity`, dropping the elements that no longer
+    /// fit as selected by `retain`
+    ///
+    /// The remaining elements keep their FIFO order
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero, or more than the cursor type `C` can count
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sringbuf::Retain;
+    ///
+    /// let mut ring_buffer: sringbuf::HeapRingBuffer<u8> = sringbuf::HeapRingBuffer::with_capacity(4);
+    /// ring_buffer.extend([1, 2, 3, 4]);
+    ///
+    /// ring_buffer.resize(3, Retain::Newest);
+    /// assert_eq!(ring_buffer.iter().copied().collect::<Vec<_>>(), [2, 3, 4]);
+    ///
+    /// ring_buffer.resize(2, Retain::Oldest);
+    /// assert_eq!(ring_buffer.iter().copied().collect::<Vec<_>>(), [2, 3]);
+    /// assert_eq!(ring_buffer.capacity(), 2);
+    /// ```
+    pub fn resize(&mut self, capacity: usize, retain: Retain) {
+        assert!(capacity > 0);
+        assert!(capacity <= C::MAX, "capacity does not fit in the cursor type");
+
+        match retain {
+            Retain::Oldest => {
+                while self.len() > capacity {
+                    self.set_len(self.len() - 1);
+                    let slot_index = self.slot_index(self.len());
+                    // SAFETY: the slot was occupied and is now outside the occupied range, so
+                    // it is dropped exactly once
+                    unsafe { self.slots_mut()[slot_index].assume_init_drop() };
+                }
+            }
+            Retain::Newest => {
+                while self.len() > capacity {
+                    self.read();
+                }
+            }
+        }
+
+        if capacity != self.capacity() {
+            self.reallocate(capacity);
+        }
+    }
+
+    /// Shrinks the capacity of a ring buffer to the larger of `min_capacity` and its length,
+    /// never dropping elements
+    ///
+    /// Does nothing if the capacity is already at or below that bound
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::HeapRingBuffer<u8> = sringbuf::HeapRingBuffer::with_capacity(8);
+    /// ring_buffer.extend([1, 2, 3]);
+    ///
+    /// ring_buffer.shrink_to(4);
+    /// assert_eq!(ring_buffer.capacity(), 4);
+    /// ring_buffer.shrink_to(0);
+    /// assert_eq!(ring_buffer.capacity(), 3);
+    /// ```
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        let capacity = min_capacity.max(self.len()).max(1);
+        if capacity < self.capacity() {
+            self.reallocate(capacity);
+        }
+    }
+
+    /// Moves the elements of a ring buffer into a new allocation of `capacity` slots, oldest
+    /// first, so the read cursor starts over at slot zero
+    ///
+    /// `capacity` must be at least one and at least the current length
+    pub(crate) fn reallocate(&mut self, capacity: usize) {
+        debug_assert!(capacity > 0 && capacity >= self.len() && capacity <= C::MAX);
+
+        let mut contents = Box::new_uninit_slice(capacity);
+        let (head, tail) = self.occupied_slots();
+        // SAFETY: the new allocation is distinct from the old one and holds at least `len`
+        // slots. The elements are moved bitwise and the old boxed slice of `MaybeUninit` is
+        // dropped without dropping them
+        unsafe {
+            let dst = contents.as_mut_ptr();
+            ptr::copy_nonoverlapping(head.as_ptr(), dst, head.len());
+            ptr::copy_nonoverlapping(tail.as_ptr(), dst.add(head.len()), tail.len());
+        }
+
+        self.contents = contents;
+        self.set_read_index(0);
+    }
+}
+
+impl<T, P, C: Cursor> Clone for HeapRingBuffer<T, P, C>
+where
+    T: Clone,
+{
+    /// Clones a ring buffer element by element into a new allocation, keeping the cursors of the
+    /// original
+    fn clone(&self) -> HeapRingBuffer<T, P, C> {
+        self.clone_into_storage(Box::new_uninit_slice(self.capacity()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{HeapRingBuffer, Retain};
+
+    use std::rc::Rc;
+
+    #[test]
+    fn debug_names_heap() {
+        let mut ring_buffer: HeapRingBuffer<u8> = HeapRingBuffer::with_capacity(2);
+
+        ring_buffer.extend([1, 2, 3]);
+        assert_eq!(
+            format!("{ring_buffer:?}"),
+            "HeapRingBuffer { contents: [2, 3], len: 2, capacity: 2, read_index: 1, write_index: 1 }"
+        );
+    }
+
+    #[test]
+    fn with_capacity_wrap_around() {
+        let mut ring_buffer: HeapRingBuffer<u8> = HeapRingBuffer::with_capacity(3);
+
+        for i in 1..=5 {
+            ring_buffer.write(i);
+        }
+
+        assert_eq!(ring_buffer.len(), 3);
+        assert!(ring_buffer.is_full());
+        assert_eq!(ring_buffer.as_slices(), (&[3][..], &[4, 5][..]));
+        assert_eq!(ring_buffer.read(), Some(3));
+        assert_eq!(ring_buffer.write_from(&[6, 7]), 1);
+        assert_eq!(ring_buffer.iter().copied().collect::<Vec<_>>(), [4, 5, 6]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn with_capacity_zero() {
+        let _ring_buffer: HeapRingBuffer<u8> = HeapRingBuffer::with_capacity(0);
+    }
+
+    #[test]
+    fn clone_and_drop() {
+        let counter = Rc::new(());
+        let mut ring_buffer: HeapRingBuffer<Rc<()>> = HeapRingBuffer::with_capacity(4);
+
+        ring_buffer.write(Rc::clone(&counter));
+        ring_buffer.write(Rc::clone(&counter));
+
+        let clone = ring_buffer.clone();
+        assert_eq!(clone, ring_buffer);
+        assert_eq!(Rc::strong_count(&counter), 5);
+
+        drop(ring_buffer);
+        drop(clone);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn resize_retain_newest_wrap_around() {
+        let mut ring_buffer: HeapRingBuffer<u8> = HeapRingBuffer::with_capacity(4);
+
+        ring_buffer.extend(1..=6);
+        ring_buffer.resize(2, Retain::Newest);
+        assert_eq!(ring_buffer.capacity(), 2);
+        assert_eq!(ring_buffer.as_slices(), (&[5, 6][..], &[][..]));
+    }
+
+    #[test]
+    fn resize_retain_oldest_wrap_around() {
+        let mut ring_buffer: HeapRingBuffer<u8> = HeapRingBuffer::with_capacity(4);
+
+        ring_buffer.extend(1..=6);
+        ring_buffer.resize(3, Retain::Oldest);
+        assert_eq!(ring_buffer.as_slices(), (&[3, 4, 5][..], &[][..]));
+        ring_buffer.write(7);
+        assert_eq!(ring_buffer.iter().copied().collect::<Vec<_>>(), [4, 5, 7]);
+    }
+
+    #[test]
+    fn resize_grow_keeps_elements() {
+        let mut ring_buffer: HeapRingBuffer<u8> = HeapRingBuffer::with_capacity(3);
+
+        ring_buffer.extend(1..=4);
+        ring_buffer.resize(5, Retain::Oldest);
+        assert_eq!(ring_buffer.capacity(), 5);
+        assert_eq!(ring_buffer.remaining(), 2);
+        assert_eq!(ring_buffer.as_slices(), (&[2, 3, 4][..], &[][..]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn resize_zero() {
+        let mut ring_buffer: HeapRingBuffer<u8> = HeapRingBuffer::with_capacity(3);
+        ring_buffer.resize(0, Retain::Newest);
+    }
+
+    #[test]
+    fn resize_drops_evicted() {
+        let counter = Rc::new(());
+        let mut ring_buffer: HeapRingBuffer<Rc<()>> = HeapRingBuffer::with_capacity(4);
+
+        ring_buffer.extend(std::iter::repeat_with(|| Rc::clone(&counter)).take(4));
+        ring_buffer.resize(3, Retain::Newest);
+        assert_eq!(Rc::strong_count(&counter), 4);
+        ring_buffer.resize(1, Retain::Oldest);
+        assert_eq!(Rc::strong_count(&counter), 2);
+    }
+
+    #[test]
+    fn shrink_to_empty() {
+        let mut ring_buffer: HeapRingBuffer<u8> = HeapRingBuffer::with_capacity(4);
+
+        ring_buffer.shrink_to(0);
+        assert_eq!(ring_buffer.capacity(), 1);
+        ring_buffer.shrink_to(2);
+        assert_eq!(ring_buffer.capacity(), 1);
+    }
+}
@@ -0,0 +1,123 @@
+//! [`io::Read`], [`io::BufRead`] and [`io::Write`] implementations for byte ring buffers
+use std::io;
+
+use crate::{Cursor, GenericRingBuffer, Storage};
+
+impl<S, P, C> io::Read for GenericRingBuffer<u8, S, P, C>
+where
+    S: Storage<u8>,
+    C: Cursor,
+{
+    /// Reads the oldest bytes that fit in `buf`; an empty ring buffer reads as the end of the
+    /// data
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        Ok(self.read_into(buf))
+    }
+}
+
+impl<S, P, C> io::BufRead for GenericRingBuffer<u8, S, P, C>
+where
+    S: Storage<u8>,
+    C: Cursor,
+{
+    /// Returns the oldest contiguous run of bytes, which ends at the wrap point if the
+    /// contents wrap around
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(self.as_slices().0)
+    }
+
+    /// Discards the oldest `amt` bytes, or every byte if there are fewer
+    fn consume(&mut self, amt: usize) {
+        let amt = amt.min(self.len());
+        self.set_read_index(self.slot_index(amt));
+        self.set_len(self.len() - amt);
+    }
+}
+
+impl<S, P, C> io::Write for GenericRingBuffer<u8, S, P, C>
+where
+    S: Storage<u8>,
+    C: Cursor,
+{
+    /// Writes as many bytes as fit in the free space; a full ring buffer writes zero bytes
+    /// rather than overwriting unread data
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Ok(self.write_from(buf))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RingBuffer;
+
+    use std::io::{self, BufRead, Read, Write};
+
+    #[test]
+    fn ring_buffer_copy_round_trip() {
+        let mut ring_buffer: RingBuffer<u8, 16> = RingBuffer::new();
+
+        let copied = io::copy(&mut &b"hello world"[..], &mut ring_buffer).unwrap();
+        assert_eq!(copied, 11);
+
+        let mut output = Vec::new();
+        io::copy(&mut ring_buffer, &mut output).unwrap();
+        assert_eq!(output, b"hello world");
+        assert!(ring_buffer.is_empty());
+    }
+
+    #[test]
+    fn ring_buffer_write_fmt_wrap_around() {
+        let mut ring_buffer: RingBuffer<u8, 8> = RingBuffer::new();
+
+        write!(ring_buffer, "abcde").unwrap();
+        let mut prefix = [0; 4];
+        Read::read_exact(&mut ring_buffer, &mut prefix).unwrap();
+        assert_eq!(&prefix, b"abcd");
+
+        write!(ring_buffer, "{}", 123_456).unwrap();
+        let lines: Vec<String> = (&mut ring_buffer).lines().map(Result::unwrap).collect();
+        assert_eq!(lines, ["e123456"]);
+    }
+
+    #[test]
+    fn ring_buffer_read_until_across_wrap() {
+        let mut ring_buffer: RingBuffer<u8, 8> = RingBuffer::new();
+
+        ring_buffer.write_from(b"xxxxx");
+        ring_buffer.consume(5);
+        ring_buffer.write_from(b"ab;cd;e");
+        assert_eq!(ring_buffer.fill_buf().unwrap(), b"ab;");
+
+        let mut record = Vec::new();
+        ring_buffer.read_until(b';', &mut record).unwrap();
+        assert_eq!(record, b"ab;");
+        record.clear();
+        ring_buffer.read_until(b';', &mut record).unwrap();
+        assert_eq!(record, b"cd;");
+        assert_eq!(ring_buffer.fill_buf().unwrap(), b"e");
+    }
+
+    #[test]
+    fn ring_buffer_consume_past_end() {
+        let mut ring_buffer: RingBuffer<u8, 4> = RingBuffer::new();
+
+        ring_buffer.write_from(b"abc");
+        ring_buffer.consume(10);
+        assert!(ring_buffer.is_empty());
+        ring_buffer.write_from(b"de");
+        assert_eq!(ring_buffer.read(), Some(b'd'));
+    }
+
+    #[test]
+    fn ring_buffer_write_all_full() {
+        let mut ring_buffer: RingBuffer<u8, 4> = RingBuffer::new();
+
+        let error = ring_buffer.write_all(b"too long").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::WriteZero);
+        assert_eq!(ring_buffer.as_slices().0, b"too ");
+    }
+}
@@ -0,0 +1,710 @@
+//! Iterators over the elements of a [`RingBuffer`](crate::RingBuffer)
+//!
+//! Every iterator over all of the elements is double-ended, so `rev` walks them from newest to
+//! oldest
+use core::fmt;
+use core::iter::FusedIterator;
+use core::slice;
+
+use crate::policy::Overwrite;
+use crate::{Cursor, GenericRingBuffer, Storage};
+
+/// An iterator over the elements of a ring buffer, from oldest to newest
+///
+/// This struct is created by [`RingBuffer::iter`](crate::RingBuffer::iter)
+#[derive(Clone, Debug)]
+pub struct Iter<'a, T> {
+    head: slice::Iter<'a, T>,
+    tail: slice::Iter<'a, T>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    pub(crate) fn new(head: &'a [T], tail: &'a [T]) -> Iter<'a, T> {
+        Iter {
+            head: head.iter(),
+            tail: tail.iter(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.head.next().or_else(|| self.tail.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.head.len() + self.tail.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.tail.next_back().or_else(|| self.head.next_back())
+    }
+}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// A mutable iterator over the elements of a ring buffer, from oldest to newest
+///
+/// This struct is created by [`RingBuffer::iter_mut`](crate::RingBuffer::iter_mut)
+#[derive(Debug)]
+pub struct IterMut<'a, T> {
+    head: slice::IterMut<'a, T>,
+    tail: slice::IterMut<'a, T>,
+}
+
+impl<'a, T> IterMut<'a, T> {
+    pub(crate) fn new(head: &'a mut [T], tail: &'a mut [T]) -> IterMut<'a, T> {
+        IterMut {
+            head: head.iter_mut(),
+            tail: tail.iter_mut(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        self.head.next().or_else(|| self.tail.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.head.len() + self.tail.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<&'a mut T> {
+        self.tail.next_back().or_else(|| self.head.next_back())
+    }
+}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// An owning iterator over the elements of a ring buffer, from oldest to newest
+///
+/// This struct is created by the [`IntoIterator`] implementation of
+/// [`RingBuffer`](crate::RingBuffer)
+pub struct IntoIter<T, S, P = Overwrite, C = usize>
+where
+    S: Storage<T>,
+    C: Cursor,
+{
+    ring_buffer: GenericRingBuffer<T, S, P, C>,
+}
+
+impl<T, S, P, C> Clone for IntoIter<T, S, P, C>
+where
+    S: Storage<T>,
+    C: Cursor,
+    GenericRingBuffer<T, S, P, C>: Clone,
+{
+    fn clone(&self) -> IntoIter<T, S, P, C> {
+        IntoIter {
+            ring_buffer: self.ring_buffer.clone(),
+        }
+    }
+}
+
+impl<T, S, P, C> fmt::Debug for IntoIter<T, S, P, C>
+where
+    T: fmt::Debug,
+    S: Storage<T>,
+    C: Cursor,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter")
+            .field(&self.ring_buffer.as_slices())
+            .finish()
+    }
+}
+
+impl<T, S, P, C> Iterator for IntoIter<T, S, P, C>
+where
+    S: Storage<T>,
+    C: Cursor,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.ring_buffer.read()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.ring_buffer.len();
+        (len, Some(len))
+    }
+}
+
+impl<T, S, P, C> ExactSizeIterator for IntoIter<T, S, P, C> where S: Storage<T>, C: Cursor {}
+
+impl<T, S, P, C> DoubleEndedIterator for IntoIter<T, S, P, C>
+where
+    S: Storage<T>,
+    C: Cursor,
+{
+    fn next_back(&mut self) -> Option<T> {
+        self.ring_buffer.pop_back()
+    }
+}
+
+impl<T, S, P, C> FusedIterator for IntoIter<T, S, P, C> where S: Storage<T>, C: Cursor {}
+
+impl<T, S, P, C> IntoIterator for GenericRingBuffer<T, S, P, C>
+where
+    S: Storage<T>,
+    C: Cursor,
+{
+    type Item = T;
+    type IntoIter = IntoIter<T, S, P, C>;
+
+    /// Consumes a ring buffer, returning an iterator over its elements from oldest to newest
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 3> = sringbuf::RingBuffer::new();
+    /// ring_buffer.write(1);
+    /// ring_buffer.write(2);
+    /// let contents: Vec<u8> = ring_buffer.into_iter().collect();
+    /// assert_eq!(contents, [1, 2]);
+    /// ```
+    fn into_iter(self) -> IntoIter<T, S, P, C> {
+        IntoIter { ring_buffer: self }
+    }
+}
+
+/// A draining iterator over the elements of a ring buffer, from oldest to newest
+///
+/// This struct is created by [`RingBuffer::drain`](crate::RingBuffer::drain). Any elements
+/// left unread when it is dropped are removed as well, leaving the ring buffer empty
+#[derive(Debug)]
+pub struct Drain<'a, T, S, P = Overwrite, C = usize>
+where
+    S: Storage<T>,
+    C: Cursor,
+{
+    ring_buffer: &'a mut GenericRingBuffer<T, S, P, C>,
+}
+
+impl<'a, T, S, P, C> Drain<'a, T, S, P, C>
+where
+    S: Storage<T>,
+    C: Cursor,
+{
+    pub(crate) fn new(ring_buffer: &'a mut GenericRingBuffer<T, S, P, C>) -> Drain<'a, T, S, P, C> {
+        Drain { ring_buffer }
+    }
+}
+
+impl<T, S, P, C> Iterator for Drain<'_, T, S, P, C>
+where
+    S: Storage<T>,
+    C: Cursor,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.ring_buffer.read()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.ring_buffer.len();
+        (len, Some(len))
+    }
+}
+
+impl<T, S, P, C> ExactSizeIterator for Drain<'_, T, S, P, C> where S: Storage<T>, C: Cursor {}
+
+impl<T, S, P, C> DoubleEndedIterator for Drain<'_, T, S, P, C>
+where
+    S: Storage<T>,
+    C: Cursor,
+{
+    fn next_back(&mut self) -> Option<T> {
+        self.ring_buffer.pop_back()
+    }
+}
+
+impl<T, S, P, C> FusedIterator for Drain<'_, T, S, P, C> where S: Storage<T>, C: Cursor {}
+
+impl<T, S, P, C> Drop for Drain<'_, T, S, P, C>
+where
+    S: Storage<T>,
+    C: Cursor,
+{
+    fn drop(&mut self) {
+        self.ring_buffer.clear();
+    }
+}
+
+/// An iterator reading the oldest elements of a ring buffer while they match a predicate
+///
+/// This struct is created by [`RingBuffer::read_while`](crate::RingBuffer::read_while). The
+/// first element that does not match is left in the ring buffer, as are any elements left
+/// unread when it is dropped
+pub struct ReadWhile<'a, T, S, F, P = Overwrite, C = usize>
+where
+    S: Storage<T>,
+    C: Cursor,
+{
+    ring_buffer: &'a mut GenericRingBuffer<T, S, P, C>,
+    predicate: F,
+    done: bool,
+}
+
+impl<'a, T, S, F, P, C> ReadWhile<'a, T, S, F, P, C>
+where
+    S: Storage<T>,
+    C: Cursor,
+{
+    pub(crate) fn new(
+        ring_buffer: &'a mut GenericRingBuffer<T, S, P, C>,
+        predicate: F,
+    ) -> ReadWhile<'a, T, S, F, P, C> {
+        ReadWhile {
+            ring_buffer,
+            predicate,
+            done: false,
+        }
+    }
+}
+
+impl<T, S, F, P, C> fmt::Debug for ReadWhile<'_, T, S, F, P, C>
+where
+    T: fmt::Debug,
+    S: Storage<T>,
+    C: Cursor,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadWhile")
+            .field("ring_buffer", &self.ring_buffer.as_slices())
+            .field("done", &self.done)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, S, F, P, C> Iterator for ReadWhile<'_, T, S, F, P, C>
+where
+    S: Storage<T>,
+    F: FnMut(&T) -> bool,
+    C: Cursor,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.done {
+            return None;
+        }
+
+        match self.ring_buffer.peek() {
+            Some(data) if (self.predicate)(data) => self.ring_buffer.read(),
+            _ => {
+                self.done = true;
+                None
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            (0, Some(self.ring_buffer.len()))
+        }
+    }
+}
+
+impl<T, S, F, P, C> FusedIterator for ReadWhile<'_, T, S, F, P, C>
+where
+    S: Storage<T>,
+    F: FnMut(&T) -> bool,
+    C: Cursor,
+{
+}
+
+/// An iterator removing the elements of a ring buffer that match a predicate, from oldest to
+/// newest
+///
+/// This struct is created by [`RingBuffer::extract_if`](crate::RingBuffer::extract_if). The
+/// elements that do not match stay in the ring buffer in FIFO order, including those not yet
+/// visited when it is dropped
+pub struct ExtractIf<'a, T, S, F, P = Overwrite, C = usize>
+where
+    S: Storage<T>,
+    C: Cursor,
+{
+    ring_buffer: &'a mut GenericRingBuffer<T, S, P, C>,
+    predicate: F,
+    index: usize,
+    kept: usize,
+    len: usize,
+}
+
+impl<'a, T, S, F, P, C> ExtractIf<'a, T, S, F, P, C>
+where
+    S: Storage<T>,
+    C: Cursor,
+{
+    pub(crate) fn new(
+        ring_buffer: &'a mut GenericRingBuffer<T, S, P, C>,
+        predicate: F,
+    ) -> ExtractIf<'a, T, S, F, P, C> {
+        let len = ring_buffer.len();
+        // The visited slots are compacted as the iterator goes, so the ring buffer is empty until
+        // it is dropped; forgetting the iterator leaks the elements rather than exposing holes
+        ring_buffer.set_len(0);
+        ExtractIf {
+            ring_buffer,
+            predicate,
+            index: 0,
+            kept: 0,
+            len,
+        }
+    }
+}
+
+impl<T, S, F, P, C> fmt::Debug for ExtractIf<'_, T, S, F, P, C>
+where
+    S: Storage<T>,
+    C: Cursor,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtractIf")
+            .field("index", &self.index)
+            .field("kept", &self.kept)
+            .field("len", &self.len)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, S, F, P, C> Iterator for ExtractIf<'_, T, S, F, P, C>
+where
+    S: Storage<T>,
+    F: FnMut(&mut T) -> bool,
+    C: Cursor,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        while self.index < self.len {
+            let slot = self.ring_buffer.slot_index(self.index);
+            // SAFETY: the slots from `index` to `len` hold the elements not yet visited
+            let data = unsafe { self.ring_buffer.slots_mut()[slot].assume_init_mut() };
+            if (self.predicate)(data) {
+                self.index += 1;
+                // SAFETY: the slot is now behind `index`, so it is treated as empty from here on
+                return Some(unsafe { self.ring_buffer.slots()[slot].assume_init_read() });
+            }
+
+            let kept_slot = self.ring_buffer.slot_index(self.kept);
+            self.ring_buffer.slots_mut().swap(kept_slot, slot);
+            self.kept += 1;
+            self.index += 1;
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.len - self.index))
+    }
+}
+
+impl<T, S, F, P, C> FusedIterator for ExtractIf<'_, T, S, F, P, C>
+where
+    S: Storage<T>,
+    F: FnMut(&mut T) -> bool,
+    C: Cursor,
+{
+}
+
+impl<T, S, F, P, C> Drop for ExtractIf<'_, T, S, F, P, C>
+where
+    S: Storage<T>,
+    C: Cursor,
+{
+    fn drop(&mut self) {
+        for index in self.index..self.len {
+            let kept_slot = self.ring_buffer.slot_index(self.kept);
+            let slot = self.ring_buffer.slot_index(index);
+            self.ring_buffer.slots_mut().swap(kept_slot, slot);
+            self.kept += 1;
+        }
+        self.ring_buffer.set_len(self.kept);
+    }
+}
+
+impl<'a, T, S, P, C> IntoIterator for &'a GenericRingBuffer<T, S, P, C>
+where
+    S: Storage<T>,
+    C: Cursor,
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T, S, P, C> IntoIterator for &'a mut GenericRingBuffer<T, S, P, C>
+where
+    S: Storage<T>,
+    C: Cursor,
+{
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RingBuffer;
+
+    use std::rc::Rc;
+
+    #[test]
+    fn iter_wrap_around() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+
+        ring_buffer.write(1);
+        ring_buffer.write(2);
+        ring_buffer.write(3);
+        ring_buffer.read();
+        ring_buffer.write(4);
+
+        let mut iter = ring_buffer.iter();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), Some(&4));
+        assert_eq!(iter.next(), None);
+        assert_eq!(ring_buffer.len(), 3);
+    }
+
+    #[test]
+    fn iter_rev_wrap_around() {
+        let mut ring_buffer: RingBuffer<u8, 4> = RingBuffer::new();
+        ring_buffer.extend([1, 2, 3, 4, 5, 6]);
+        assert_eq!(ring_buffer.as_slices(), (&[3, 4][..], &[5, 6][..]));
+
+        assert!(ring_buffer.iter().rev().eq(&[6, 5, 4, 3]));
+
+        let mut iter = ring_buffer.iter_mut();
+        assert_eq!(iter.next(), Some(&mut 3));
+        assert_eq!(iter.next_back(), Some(&mut 6));
+        assert_eq!(iter.next_back(), Some(&mut 5));
+        assert_eq!(iter.next(), Some(&mut 4));
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn into_iter_rev() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+        ring_buffer.extend([1, 2, 3, 4]);
+
+        let mut drain = ring_buffer.drain();
+        assert_eq!(drain.next_back(), Some(4));
+        assert_eq!(drain.next(), Some(2));
+        drop(drain);
+
+        ring_buffer.extend([5, 6, 7]);
+        assert!(ring_buffer.into_iter().rev().eq([7, 6, 5]));
+    }
+
+    #[test]
+    fn iter_empty() {
+        let ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+
+        assert_eq!(ring_buffer.iter().next(), None);
+    }
+
+    #[test]
+    fn iter_mut_wrap_around() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+
+        ring_buffer.write(1);
+        ring_buffer.write(2);
+        ring_buffer.write(3);
+        ring_buffer.read();
+        ring_buffer.write(4);
+
+        for data in ring_buffer.iter_mut() {
+            *data *= 10;
+        }
+
+        assert_eq!(ring_buffer.read(), Some(20));
+        assert_eq!(ring_buffer.read(), Some(30));
+        assert_eq!(ring_buffer.read(), Some(40));
+        assert_eq!(ring_buffer.read(), None);
+    }
+
+    #[test]
+    fn into_iter_wrap_around() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+
+        ring_buffer.write(1);
+        ring_buffer.write(2);
+        ring_buffer.read();
+        ring_buffer.write(3);
+        ring_buffer.write(4);
+
+        let mut iter = ring_buffer.into_iter();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next(), Some(2));
+
+        let mut rest = [0; 2];
+        for (slot, data) in rest.iter_mut().zip(&mut iter) {
+            *slot = data;
+        }
+        assert_eq!(rest, [3, 4]);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn into_iter_references() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+
+        ring_buffer.write(1);
+        ring_buffer.write(2);
+
+        for data in &mut ring_buffer {
+            *data += 1;
+        }
+
+        let mut sum = 0;
+        for data in &ring_buffer {
+            sum += *data;
+        }
+        assert_eq!(sum, 5);
+    }
+
+    #[test]
+    fn drain_all() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+
+        ring_buffer.write(1);
+        ring_buffer.write(2);
+        ring_buffer.read();
+        ring_buffer.write(3);
+        ring_buffer.write(4);
+
+        let mut drain = ring_buffer.drain();
+        assert_eq!(drain.len(), 3);
+        assert_eq!(drain.next(), Some(2));
+        assert_eq!(drain.next(), Some(3));
+        assert_eq!(drain.next(), Some(4));
+        assert_eq!(drain.next(), None);
+        drop(drain);
+
+        assert!(ring_buffer.is_empty());
+    }
+
+    #[test]
+    fn drain_dropped_early() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+
+        ring_buffer.write(1);
+        ring_buffer.write(2);
+        ring_buffer.write(3);
+
+        assert_eq!(ring_buffer.drain().next(), Some(1));
+        assert!(ring_buffer.is_empty());
+        assert_eq!(ring_buffer.read(), None);
+
+        ring_buffer.write(4);
+        assert_eq!(ring_buffer.read(), Some(4));
+    }
+
+    #[test]
+    fn drain_drops_unread() {
+        let counter = Rc::new(());
+        let mut ring_buffer: RingBuffer<Rc<()>, 3> = RingBuffer::new();
+
+        ring_buffer.write(Rc::clone(&counter));
+        ring_buffer.write(Rc::clone(&counter));
+        ring_buffer.write(Rc::clone(&counter));
+
+        let mut drain = ring_buffer.drain();
+        let first = drain.next();
+        drop(drain);
+
+        assert_eq!(Rc::strong_count(&counter), 2);
+        drop(first);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn into_iter_drops_unread() {
+        let counter = Rc::new(());
+        let mut ring_buffer: RingBuffer<Rc<()>, 3> = RingBuffer::new();
+
+        ring_buffer.write(Rc::clone(&counter));
+        ring_buffer.write(Rc::clone(&counter));
+
+        let mut iter = ring_buffer.into_iter();
+        iter.next();
+        drop(iter);
+
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn read_while_stops_at_first_mismatch() {
+        let mut ring_buffer: RingBuffer<u8, 4> = RingBuffer::new();
+        ring_buffer.extend([1, 2, 3, 4, 5, 1]);
+
+        let mut iter = ring_buffer.read_while(|data| *data < 5);
+        assert_eq!(iter.next(), Some(3));
+        assert_eq!(iter.next(), Some(4));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+        assert!(ring_buffer.iter().eq(&[5, 1]));
+
+        assert_eq!(ring_buffer.read_while(|_| true).next(), Some(5));
+        assert!(ring_buffer.iter().eq(&[1]));
+    }
+
+    #[test]
+    fn extract_if_wrap_around() {
+        let mut ring_buffer: RingBuffer<String, 5> = RingBuffer::new();
+        ring_buffer.extend(["a", "B", "c", "D", "e", "F", "g"].map(String::from));
+
+        let extracted: Vec<String> = ring_buffer
+            .extract_if(|data| data.chars().all(char::is_uppercase))
+            .collect();
+        assert_eq!(extracted, ["D", "F"]);
+        assert!(ring_buffer.iter().eq(["c", "e", "g"]));
+        ring_buffer.extend(["h", "i"].map(String::from));
+        assert!(ring_buffer.iter().eq(["c", "e", "g", "h", "i"]));
+    }
+
+    #[test]
+    fn extract_if_dropped_early() {
+        let counter = Rc::new(());
+        let mut ring_buffer: RingBuffer<(u8, Rc<()>), 4> = RingBuffer::new();
+        ring_buffer.extend((0..6).map(|index| (index, Rc::clone(&counter))));
+
+        let mut iter = ring_buffer.extract_if(|(index, _)| *index % 2 == 0);
+        assert_eq!(iter.next().map(|(index, _)| index), Some(2));
+        assert_eq!(Rc::strong_count(&counter), 4);
+        drop(iter);
+
+        assert!(ring_buffer.iter().map(|(index, _)| *index).eq([3, 4, 5]));
+        ring_buffer.clear();
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+}
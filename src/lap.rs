@@ -0,0 +1,72 @@
+//! Stamped slots and lap-counting cursors shared by the lock-free queues
+//!
+//! A cursor holds a slot index in its low bits and a lap count above them. Each slot's stamp is
+//! the cursor value at which it may next be written, or that value plus one once it holds an
+//! element, so threads learn the state of a slot from the slot alone
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use core::sync::atomic::AtomicUsize;
+
+/// A slot of a lock-free queue with the stamp telling threads whether it is ready to be written
+/// or read
+pub(crate) struct Slot<T> {
+    pub(crate) stamp: AtomicUsize,
+    pub(crate) data: UnsafeCell<MaybeUninit<T>>,
+}
+
+/// Returns `N` empty slots, each ready to be written in the first lap
+pub(crate) const fn slots<T, const N: usize>() -> [Slot<T>; N] {
+    let mut slots = [const {
+        Slot {
+            stamp: AtomicUsize::new(0),
+            data: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }; N];
+
+    let mut i = 0;
+    while i < N {
+        slots[i].stamp = AtomicUsize::new(i);
+        i += 1;
+    }
+
+    slots
+}
+
+/// Cursor arithmetic for a queue of `N` slots
+pub(crate) struct Lap<const N: usize>;
+
+impl<const N: usize> Lap<N> {
+    /// The cursor increment that moves from one lap to the next, leaving room below it for
+    /// every slot index
+    pub(crate) const ONE: usize = (N + 1).next_power_of_two();
+
+    /// Returns the slot index a cursor points to
+    pub(crate) fn index(position: usize) -> usize {
+        position & (Lap::<N>::ONE - 1)
+    }
+
+    /// Returns the cursor following `position`, moving to the next lap after the last slot
+    pub(crate) fn next(position: usize) -> usize {
+        if Lap::<N>::index(position) + 1 < N {
+            position + 1
+        } else {
+            (position & !(Lap::<N>::ONE - 1)).wrapping_add(Lap::<N>::ONE)
+        }
+    }
+
+    /// Returns the number of elements between the cursors `head` and `tail`
+    pub(crate) fn distance(head: usize, tail: usize) -> usize {
+        let head_index = Lap::<N>::index(head);
+        let tail_index = Lap::<N>::index(tail);
+
+        if head_index < tail_index {
+            tail_index - head_index
+        } else if head_index > tail_index {
+            N - head_index + tail_index
+        } else if tail == head {
+            0
+        } else {
+            N
+        }
+    }
+}
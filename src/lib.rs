@@ -1,19 +1,157 @@
 //! # sringbuf
 //!
 //! `sringbuf` is a small ring buffer implementation
-#[derive(Clone, Debug, PartialEq)]
-pub struct RingBuffer<T, const N: usize> {
-    contents: [Option<T>; N],
-    read_index: usize,
-    write_index: usize,
-}
+//!
+//! The crate is `no_std`. The `alloc` feature adds the heap-backed ring buffers and the
+//! `std` feature, enabled by default, adds the blocking wrappers, channels and `std::io`
+//! implementations
+//!
+//! Specialized ring buffers such as [`SequencedRingBuffer`] or [`StatsRingBuffer`] wrap a plain
+//! one and only take over the methods that change its contents: every read-only method is
+//! available through `Deref` to the ring buffer they wrap
+#![cfg_attr(not(test), no_std)]
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::mem::{self, MaybeUninit};
+use core::ops::{Index, IndexMut};
+use core::ptr;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(all(feature = "std", not(test)))]
+extern crate std;
+
+mod aggregate;
+#[cfg(feature = "tokio")]
+mod async_io;
+mod average;
+#[cfg(feature = "std")]
+mod blocking;
+#[cfg(feature = "std")]
+pub mod broadcast;
+#[cfg(feature = "bytes")]
+mod buf;
+#[cfg(feature = "std")]
+pub mod channel;
+pub mod cursor;
+mod decimate;
+mod dedup;
+#[cfg(feature = "alloc")]
+mod deque;
+#[cfg(feature = "embedded-io")]
+mod embedded;
+mod error;
+mod evict;
+#[cfg(feature = "defmt")]
+mod format;
+#[cfg(feature = "arbitrary")]
+mod fuzz;
+mod grant;
+#[cfg(feature = "alloc")]
+mod growable;
+#[cfg(feature = "alloc")]
+mod heap;
+#[cfg(feature = "std")]
+mod io;
+mod iter;
+#[cfg(target_has_atomic = "ptr")]
+mod lap;
+#[cfg(feature = "metrics")]
+mod metered;
+#[cfg(feature = "ufmt")]
+mod micro_fmt;
+#[cfg(target_has_atomic = "ptr")]
+pub mod mpmc;
+#[cfg(target_has_atomic = "ptr")]
+pub mod mpsc;
+#[cfg(target_has_atomic = "ptr")]
+mod padding;
+pub mod policy;
+mod reservoir;
+mod sequenced;
+#[cfg(feature = "serde")]
+mod serialize;
+mod snapshot;
+#[cfg(target_has_atomic = "ptr")]
+pub mod spsc;
+mod stats;
+mod storage;
+#[cfg(feature = "std")]
+mod sync;
+mod text;
+pub mod timed;
+#[cfg(all(feature = "futures", target_has_atomic = "ptr"))]
+mod stream;
+mod view;
+#[cfg(target_has_atomic = "ptr")]
+mod waker;
+
+pub use aggregate::AggregatingRingBuffer;
+pub use average::MovingAverage;
+#[cfg(feature = "std")]
+pub use blocking::BlockingRingBuffer;
+pub use cursor::Cursor;
+pub use decimate::DecimatingRingBuffer;
+pub use dedup::{DedupRingBuffer, DedupWrite};
+pub use error::{CapacityError, Full, Lagged, SnapshotError, Timeout};
+pub use evict::EvictingRingBuffer;
+pub use grant::WriteGrant;
+#[cfg(feature = "alloc")]
+pub use growable::GrowableRingBuffer;
+#[cfg(feature = "alloc")]
+pub use heap::{HeapRingBuffer, Retain};
+pub use iter::{Drain, ExtractIf, IntoIter, Iter, IterMut, ReadWhile};
+#[cfg(feature = "metrics")]
+pub use metered::MeteredRingBuffer;
+pub use reservoir::ReservoirBuffer;
+pub use sequenced::SequencedRingBuffer;
+pub use snapshot::Encode;
+pub use stats::{Stats, StatsRingBuffer};
+pub use storage::{Storage, UninitStorage};
+pub use timed::TimedRingBuffer;
+pub use view::{RingBufferSlice, RingBufferView};
+#[cfg(target_has_atomic = "ptr")]
+pub use waker::AtomicWaker;
+
+use policy::{OverflowPolicy, Overwrite};
+use policy::sealed::Overflow;
 
-impl<T, const N: usize> RingBuffer<T, N> 
+/// A fixed-capacity FIFO ring buffer holding up to `N` elements inline
+///
+/// Elements live in uninitialized slots tracked by a read cursor and a length, so no space is
+/// spent marking which slots are in use. Capacities that are a power of two wrap their cursors
+/// with a bit mask, which makes them the fastest choice for hot loops
+pub type RingBuffer<T, const N: usize, P = Overwrite, C = usize> =
+    GenericRingBuffer<T, [MaybeUninit<T>; N], P, C>;
+
+/// A FIFO ring buffer over any [`Storage`]
+///
+/// Every ring buffer type in this crate is an alias of this one, so they all share the same
+/// methods. Most code should name [`RingBuffer`], [`HeapRingBuffer`] or [`RingBufferView`] instead
+///
+/// The read cursor and length are stored as a [`Cursor`], `usize` by default, which also caps
+/// the capacity
+pub struct GenericRingBuffer<T, S, P = Overwrite, C = usize>
 where
-    T: Copy
+    S: Storage<T>,
+    C: Cursor,
 {
+    contents: S,
+    read_index: C,
+    len: C,
+    marker: PhantomData<(T, P)>,
+}
+
+impl<T, const N: usize, P, C: Cursor> RingBuffer<T, N, P, C> {
     /// Creates a new RingBuffer instance
     ///
+    /// A capacity of zero, or one the cursor type `C` cannot count, is rejected at compile time
+    ///
     /// # Examples
     /// 
     /// ```
@@ -22,33 +160,386 @@ where
     /// const num: usize = 5;
     /// let ring_buffer: sringbuf::RingBuffer<char, num> = sringbuf::RingBuffer::new();
     /// ```
-    pub const fn new() -> RingBuffer<T, N> {
-        assert!(N > 0);
+    ///
+    /// ```compile_fail
+    /// let ring_buffer: sringbuf::RingBuffer<char, 0> = sringbuf::RingBuffer::new();
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use sringbuf::policy::Overwrite;
+    ///
+    /// let ring_buffer: sringbuf::RingBuffer<u8, 256, Overwrite, u8> = sringbuf::RingBuffer::new();
+    /// ```
+    pub const fn new() -> RingBuffer<T, N, P, C> {
+        const {
+            assert!(N > 0, "ring buffer capacity must not be zero");
+            assert!(N <= C::MAX, "capacity does not fit in the cursor type");
+        }
 
         RingBuffer {
-            contents: [None; N],
-            read_index: 0,
-            write_index: 0,
+            contents: [const { MaybeUninit::uninit() }; N],
+            read_index: C::ZERO,
+            len: C::ZERO,
+            marker: PhantomData,
         }
     }
 
-    /// Writes a value to the beginning of a ring buffer
+    /// Splits a ring buffer in two at logical position `at`, keeping the older elements and
+    /// returning a new ring buffer with the elements from `at` onward
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at` is more than the length
     ///
     /// # Examples
     ///
     /// ```
-    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 5> = sringbuf::RingBuffer::new();
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 4> = sringbuf::RingBuffer::new();
+    /// ring_buffer.extend([1, 2, 3, 4, 5]);
+    /// let newer = ring_buffer.split_off(1);
+    /// assert!(ring_buffer.iter().eq(&[2]));
+    /// assert!(newer.iter().eq(&[3, 4, 5]));
+    /// ```
+    pub fn split_off(&mut self, at: usize) -> RingBuffer<T, N, P, C> {
+        let mut other = RingBuffer::new();
+        self.split_off_into(at, &mut other);
+        other
+    }
+
+    /// Creates a new RingBuffer instance holding clones of the last `N` of `values`, the first
+    /// one kept being the oldest
+    ///
+    /// This is the lossy counterpart of the `TryFrom<&[T]>` implementation, which fails instead
+    /// of dropping the older values
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let ring_buffer = sringbuf::RingBuffer::<u8, 3>::from_slice_newest(&[1, 2, 3, 4, 5]);
+    /// assert!(ring_buffer.iter().eq(&[3, 4, 5]));
+    /// ```
+    pub fn from_slice_newest(values: &[T]) -> RingBuffer<T, N, P, C>
+    where
+        T: Clone,
+    {
+        let newest = &values[values.len().saturating_sub(N)..];
+        match RingBuffer::try_from(newest) {
+            Ok(ring_buffer) => ring_buffer,
+            Err(_) => unreachable!("at most `N` values are left"),
+        }
+    }
+}
+
+impl<T, const N: usize, P> RingBuffer<T, N, P> {
+    /// Creates a new RingBuffer instance holding `values`, the first one being the oldest
+    ///
+    /// This is a `const fn`, so pre-populated ring buffers can be built at compile time and
+    /// placed in a `static`. Passing more values than the capacity is rejected at compile time
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// static HISTORY: sringbuf::RingBuffer<u16, 4> = sringbuf::RingBuffer::new_with([10, 20, 30]);
+    ///
+    /// assert_eq!(HISTORY.len(), 3);
+    /// assert_eq!(HISTORY.peek(), Some(&10));
+    /// ```
+    ///
+    /// ```compile_fail
+    /// let ring_buffer: sringbuf::RingBuffer<u8, 2> = sringbuf::RingBuffer::new_with([1, 2, 3]);
+    /// ```
+    pub const fn new_with<const M: usize>(values: [T; M]) -> RingBuffer<T, N, P> {
+        const { assert!(M <= N, "more values than the ring buffer capacity") }
+
+        let mut ring_buffer = RingBuffer::new();
+        let values = MaybeUninit::new(values);
+        // SAFETY: the first `M` slots are free and do not overlap `values`, which is wrapped in
+        // `MaybeUninit` so the moved elements are not dropped twice
+        unsafe {
+            ptr::copy_nonoverlapping(
+                values.as_ptr().cast::<T>(),
+                ring_buffer.contents.as_mut_ptr().cast::<T>(),
+                M,
+            );
+        }
+        ring_buffer.len = M;
+        ring_buffer
+    }
+
+    /// Creates a new, full RingBuffer instance holding `values`, the first one being the oldest
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// const RING_BUFFER: sringbuf::RingBuffer<u8, 3> = sringbuf::RingBuffer::new_from_array([1, 2, 3]);
+    ///
+    /// let mut ring_buffer = RING_BUFFER;
+    /// assert!(ring_buffer.is_full());
+    /// assert_eq!(ring_buffer.write(4), Some(1));
+    /// ```
+    pub const fn new_from_array(values: [T; N]) -> RingBuffer<T, N, P> {
+        RingBuffer::new_with(values)
+    }
+}
+
+impl<T, S, P, C> GenericRingBuffer<T, S, P, C>
+where
+    S: Storage<T>,
+    C: Cursor,
+{
+    /// Creates a new, empty ring buffer over `contents`, using every slot of it
+    ///
+    /// Whatever `contents` already holds is treated as uninitialized and never dropped
+    ///
+    /// # Panics
+    ///
+    /// Panics if `contents` has no slots, or more than the cursor type `C` can count
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::mem::MaybeUninit;
+    ///
+    /// let mut slots = [const { MaybeUninit::uninit() }; 4];
+    /// let mut ring_buffer: sringbuf::GenericRingBuffer<u8, &mut [MaybeUninit<u8>]> =
+    ///     sringbuf::GenericRingBuffer::from_storage(&mut slots[..]);
     /// ring_buffer.write(1);
+    /// assert_eq!(ring_buffer.capacity(), 4);
+    /// assert_eq!(ring_buffer.read(), Some(1));
     /// ```
-    pub fn write(&mut self, data: T) {
-        self.contents[self.write_index] = Some(data);
+    pub fn from_storage(contents: S) -> GenericRingBuffer<T, S, P, C> {
+        assert!(!contents.as_slice().is_empty());
+        assert!(
+            contents.as_slice().len() <= C::MAX,
+            "capacity does not fit in the cursor type"
+        );
 
-        if self.write_index + 1 == N {
-            self.write_index = 0;
-            return;
+        GenericRingBuffer {
+            contents,
+            read_index: C::ZERO,
+            len: C::ZERO,
+            marker: PhantomData,
+        }
+    }
+
+    /// Writes a value to the beginning of a ring buffer
+    ///
+    /// If the ring buffer is full, the outcome depends on its [`policy`]: by default the oldest
+    /// element is overwritten and the read cursor moves past it, so reads stay in FIFO order,
+    /// [`policy::Reject`] discards `data` and [`policy::Panic`] panics. The element that did not
+    /// make it into the ring buffer, either the overwritten one or the rejected `data`, is
+    /// returned
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 2> = sringbuf::RingBuffer::new();
+    /// assert_eq!(ring_buffer.write(1), None);
+    /// assert_eq!(ring_buffer.write(2), None);
+    /// assert_eq!(ring_buffer.write(3), Some(1));
+    /// assert_eq!(ring_buffer.read(), Some(2));
+    /// assert_eq!(ring_buffer.read(), Some(3));
+    /// ```
+    pub fn write(&mut self, data: T) -> Option<T>
+    where
+        P: OverflowPolicy,
+    {
+        if self.is_full() {
+            match P::OVERFLOW {
+                Overflow::Overwrite => {}
+                Overflow::Reject => return Some(data),
+                Overflow::Panic => panic!("write to a full ring buffer"),
+            }
+
+            let read_index = self.read_index();
+            let slot = mem::replace(&mut self.slots_mut()[read_index], MaybeUninit::new(data));
+            self.set_read_index(self.slot_index(1));
+            // SAFETY: the buffer was full, so the slot at `read_index` held the oldest element
+            return Some(unsafe { slot.assume_init() });
+        }
+
+        let write_index = self.write_index();
+        self.slots_mut()[write_index] = MaybeUninit::new(data);
+        self.set_len(self.len() + 1);
+        None
+    }
+
+    /// Builds a value in place at the beginning of a ring buffer by handing `init` the slot to
+    /// fill, which avoids moving large values into the ring buffer
+    ///
+    /// A full ring buffer behaves as in [`RingBuffer::write`]: by default the oldest element is
+    /// evicted and returned in `Ok`, [`policy::Reject`] returns [`Full`] without calling `init`
+    /// and [`policy::Panic`] panics. The oldest element is only evicted once `init` returns, so
+    /// if `init` panics the ring buffer is left as it was
+    ///
+    /// # Safety
+    ///
+    /// `init` must initialize the slot it is given before returning
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<[u8; 1024], 2> = sringbuf::RingBuffer::new();
+    /// // SAFETY: the closure initializes the slot
+    /// let evicted = unsafe {
+    ///     ring_buffer.write_with(|slot| {
+    ///         slot.write([7; 1024]);
+    ///     })
+    /// };
+    /// assert_eq!(evicted, Ok(None));
+    /// assert_eq!(ring_buffer.read().map(|data| data[1023]), Some(7));
+    /// ```
+    pub unsafe fn write_with(
+        &mut self,
+        init: impl FnOnce(&mut MaybeUninit<T>),
+    ) -> Result<Option<T>, Full<()>>
+    where
+        P: OverflowPolicy,
+    {
+        /// Puts the evicted element back in its slot if `init` unwinds
+        struct Restore<T> {
+            slot: *mut MaybeUninit<T>,
+            oldest: Option<T>,
+        }
+
+        impl<T> Drop for Restore<T> {
+            fn drop(&mut self) {
+                if let Some(oldest) = self.oldest.take() {
+                    // SAFETY: `slot` outlives the guard and nothing else refers to it
+                    unsafe { (*self.slot).write(oldest) };
+                }
+            }
+        }
+
+        let full = self.is_full();
+        if full {
+            match P::OVERFLOW {
+                Overflow::Overwrite => {}
+                Overflow::Reject => return Err(Full(())),
+                Overflow::Panic => panic!("write to a full ring buffer"),
+            }
+        }
+
+        let write_index = self.write_index();
+        let slot: *mut MaybeUninit<T> = &mut self.slots_mut()[write_index];
+        if !full {
+            // SAFETY: `slot` points into the storage, which `self` borrows mutably
+            init(unsafe { &mut *slot });
+            self.set_len(self.len() + 1);
+            return Ok(None);
+        }
+
+        // SAFETY: the buffer is full, so the write index is the read index and the slot holds
+        // the oldest element, which the guard owns until `init` returns
+        let mut restore = Restore {
+            slot,
+            oldest: Some(unsafe { (*slot).assume_init_read() }),
+        };
+        // SAFETY: as above
+        init(unsafe { &mut *slot });
+        let oldest = restore.oldest.take();
+        self.set_read_index(self.slot_index(1));
+        Ok(oldest)
+    }
+
+    /// Writes `T::default()` to the beginning of a ring buffer and returns a mutable reference
+    /// to it, so the element can be filled in place without `unsafe`
+    ///
+    /// Overflow is handled as in [`write_with`](GenericRingBuffer::write_with), and the evicted
+    /// element, if any, is returned alongside the reference
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<[u8; 32], 1> = sringbuf::RingBuffer::new();
+    /// let (frame, evicted) = ring_buffer.write_default().unwrap();
+    /// frame[..3].copy_from_slice(b"abc");
+    /// assert!(evicted.is_none());
+    /// assert_eq!(&ring_buffer.read().unwrap()[..4], b"abc\0");
+    /// ```
+    pub fn write_default(&mut self) -> Result<(&mut T, Option<T>), Full<()>>
+    where
+        T: Default,
+        P: OverflowPolicy,
+    {
+        // SAFETY: the closure initializes the slot
+        let evicted = unsafe {
+            self.write_with(|slot| {
+                slot.write(T::default());
+            })
+        }?;
+
+        let index = self.last_index();
+        // SAFETY: the element at `index` was just written
+        let last = unsafe { self.slots_mut()[index].assume_init_mut() };
+        Ok((last, evicted))
+    }
+
+    /// Writes a value to a ring buffer unless `merge` folds it into the newest element
+    ///
+    /// `merge` is called with the newest element and `data`; it returns `Ok(())` once it has
+    /// merged them, or hands `data` back in `Err` to have it written as a new element as
+    /// [`RingBuffer::write`] would. An empty ring buffer writes `data` without calling `merge`.
+    /// The element that did not make it into the ring buffer, if any, is returned
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::ops::Range;
+    ///
+    /// fn adjacent(last: &mut Range<u32>, data: Range<u32>) -> Result<(), Range<u32>> {
+    ///     if last.end != data.start {
+    ///         return Err(data);
+    ///     }
+    ///     last.end = data.end;
+    ///     Ok(())
+    /// }
+    ///
+    /// let mut ring_buffer: sringbuf::RingBuffer<Range<u32>, 4> = sringbuf::RingBuffer::new();
+    /// ring_buffer.write_coalesce(0..4, adjacent);
+    /// ring_buffer.write_coalesce(4..8, adjacent);
+    /// ring_buffer.write_coalesce(10..12, adjacent);
+    /// assert!(ring_buffer.iter().eq(&[0..8, 10..12]));
+    /// ```
+    pub fn write_coalesce(
+        &mut self,
+        data: T,
+        merge: impl FnOnce(&mut T, T) -> Result<(), T>,
+    ) -> Option<T>
+    where
+        P: OverflowPolicy,
+    {
+        let data = match self.last_mut() {
+            Some(last) => match merge(last, data) {
+                Ok(()) => return None,
+                Err(data) => data,
+            },
+            None => data,
+        };
+        self.write(data)
+    }
+
+    /// Writes a value to a ring buffer only if it has free space, handing the value back in
+    /// [`Full`] otherwise instead of overwriting unread data
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 1> = sringbuf::RingBuffer::new();
+    /// assert_eq!(ring_buffer.try_write(1), Ok(()));
+    /// assert_eq!(ring_buffer.try_write(2), Err(sringbuf::Full(2)));
+    /// assert_eq!(ring_buffer.read(), Some(1));
+    /// ```
+    pub fn try_write(&mut self, data: T) -> Result<(), Full<T>>
+    where
+        P: OverflowPolicy,
+    {
+        if self.is_full() {
+            return Err(Full(data));
         }
 
-        self.write_index += 1;
+        self.write(data);
+        Ok(())
     }
 
     /// Reads the oldest available element a ring buffer
@@ -60,119 +551,2699 @@ where
     /// ring_buffer.write(1);
     /// let data = ring_buffer.read();
     pub fn read(&mut self) -> Option<T> {
-        let data = self.contents[self.read_index];
-        match data {
-            Some(_) => {
-                self.contents[self.read_index] = None;
-                if self.read_index + 1 == N {
-                    self.read_index = 0;
-                    return data;
-                }
-
-                self.read_index += 1;
-                data
-            }
-            None => None,
+        if self.is_empty() {
+            return None;
         }
+
+        // SAFETY: the buffer is not empty, so the slot at `read_index` holds the oldest element
+        let data = unsafe { self.slots()[self.read_index()].assume_init_read() };
+        self.set_read_index(self.slot_index(1));
+        self.set_len(self.len() - 1);
+        Some(data)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Moves all elements of `other` to the end of a ring buffer, oldest first, leaving `other`
+    /// empty
+    ///
+    /// The elements are moved in a few contiguous runs. If they do not all fit, the outcome
+    /// depends on the [`policy`] of this ring buffer: by default the oldest elements of both are
+    /// dropped as repeated writes would, [`policy::Reject`] moves only the elements that fit and
+    /// leaves the rest in `other`, and [`policy::Panic`] panics without moving anything
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 4> = sringbuf::RingBuffer::new();
+    /// let mut other: sringbuf::RingBuffer<u8, 3> = sringbuf::RingBuffer::new();
+    /// ring_buffer.extend([1, 2]);
+    /// other.extend([3, 4, 5]);
+    /// ring_buffer.append(&mut other);
+    /// assert!(ring_buffer.iter().eq(&[2, 3, 4, 5]));
+    /// assert!(other.is_empty());
+    /// ```
+    pub fn append<S2, P2, C2>(&mut self, other: &mut GenericRingBuffer<T, S2, P2, C2>)
+    where
+        P: OverflowPolicy,
+        S2: Storage<T>,
+        C2: Cursor,
+    {
+        let count = if other.len() > self.remaining() {
+            match P::OVERFLOW {
+                Overflow::Overwrite => {
+                    let excess = self.len() + other.len() - self.capacity();
+                    let evicted = self.advance_read(excess);
+                    other.advance_read(excess - evicted);
+                    other.len()
+                }
+                Overflow::Reject => self.remaining(),
+                Overflow::Panic => panic!("write to a full ring buffer"),
+            }
+        } else {
+            other.len()
+        };
 
-    #[test]
-    fn new_valid_inputs() {
-        let ring_buffer: RingBuffer<char, 5> = RingBuffer::new();
+        let mut moved = 0;
+        while moved < count {
+            let (src, _) = other.as_slices();
+            let (dst, _) = self.free_slots_mut();
+            let run = src.len().min(dst.len()).min(count - moved);
+            // SAFETY: the oldest `run` elements of `other` are moved into free slots of this
+            // ring buffer, then dropped from `other` without running their destructors
+            unsafe { ptr::copy_nonoverlapping(src.as_ptr(), dst.as_mut_ptr().cast::<T>(), run) };
 
-        assert_eq!(ring_buffer, RingBuffer {
-            contents: [None; 5],
-            read_index: 0,
-            write_index: 0,
-        });
+            self.set_len(self.len() + run);
+            other.set_read_index(other.slot_index(run));
+            other.set_len(other.len() - run);
+            moved += run;
+        }
     }
 
-    #[test]
-    #[should_panic]
-    fn new_invalid_input() {
-        let _ring_buffer: RingBuffer<char, 0> = RingBuffer::new();
+    /// Copies as many elements from `src` as fit in the free space of a ring buffer, returning
+    /// the number of elements written
+    ///
+    /// Unlike [`RingBuffer::write`], this never overwrites unread data. The elements are copied
+    /// in at most two contiguous runs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 3> = sringbuf::RingBuffer::new();
+    /// assert_eq!(ring_buffer.write_from(&[1, 2, 3, 4]), 3);
+    /// assert_eq!(ring_buffer.read(), Some(1));
+    /// ```
+    pub fn write_from(&mut self, src: &[T]) -> usize
+    where
+        T: Copy,
+    {
+        let count = src.len().min(self.remaining());
+        let (head, tail) = self.free_slots_mut();
+        let (src_head, src_tail) = src[..count].split_at(count.min(head.len()));
+
+        write_slice(&mut head[..src_head.len()], src_head);
+        write_slice(&mut tail[..src_tail.len()], src_tail);
+
+        self.set_len(self.len() + count);
+        count
     }
 
-    #[test]
-    fn write_valid_inputs_first() {
-        let mut ring_buffer: RingBuffer<char, 5> = RingBuffer::new();
+    /// Copies all of `src` into the free space of a ring buffer, or writes nothing and hands
+    /// `src` back if it does not fit
+    ///
+    /// Like [`RingBuffer::write_from`], this never overwrites unread data
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 4> = sringbuf::RingBuffer::new();
+    /// assert!(ring_buffer.write_all_or_nothing(&[1, 2, 3]).is_ok());
+    /// let error = ring_buffer.write_all_or_nothing(&[4, 5]).unwrap_err();
+    /// assert_eq!(error.into_inner(), &[4, 5]);
+    /// assert_eq!(ring_buffer.len(), 3);
+    /// ```
+    pub fn write_all_or_nothing<'a>(&mut self, src: &'a [T]) -> Result<(), Full<&'a [T]>>
+    where
+        T: Copy,
+    {
+        if src.len() > self.remaining() {
+            return Err(Full(src));
+        }
 
-        ring_buffer.write('a');
-        
-        assert_eq!(ring_buffer, RingBuffer {
-            contents: [Some('a'), None, None, None, None],
-            read_index: 0,
-            write_index: 1,
-        });
+        self.write_from(src);
+        Ok(())
     }
 
-    #[test]
-    fn write_wrap_around() {
-        let mut ring_buffer: RingBuffer<u8, 5> = RingBuffer::new();
+    /// Moves as many of the oldest elements of a ring buffer into `dst` as fit, returning the
+    /// number of elements read
+    ///
+    /// The elements are copied in at most two contiguous runs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 3> = sringbuf::RingBuffer::new();
+    /// ring_buffer.write(1);
+    /// ring_buffer.write(2);
+    /// let mut dst = [0; 4];
+    /// assert_eq!(ring_buffer.read_into(&mut dst), 2);
+    /// assert_eq!(dst, [1, 2, 0, 0]);
+    /// ```
+    pub fn read_into(&mut self, dst: &mut [T]) -> usize
+    where
+        T: Copy,
+    {
+        let count = dst.len().min(self.len());
+        let (head, tail) = self.as_slices();
+        let (dst_head, dst_tail) = dst[..count].split_at_mut(count.min(head.len()));
 
-        ring_buffer.write(1);
-        ring_buffer.write(2);
-        ring_buffer.write(3);
-        ring_buffer.write(4);
-        ring_buffer.write(5);
-        ring_buffer.write(6);
-        ring_buffer.write(7);
+        dst_head.copy_from_slice(&head[..dst_head.len()]);
+        dst_tail.copy_from_slice(&tail[..dst_tail.len()]);
 
-        assert_eq!(ring_buffer, RingBuffer {
-            contents: [Some(6), Some(7), Some(3), Some(4), Some(5)],
-            read_index: 0,
-            write_index: 2,
-        });
+        self.set_read_index(self.slot_index(count));
+        self.set_len(self.len() - count);
+        count
     }
 
-    #[test]
-    fn read_first() {
-        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+    /// Removes exactly `M` of the oldest elements of a ring buffer and returns them as an array,
+    /// or returns `None` and leaves the ring buffer untouched if it holds fewer
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 8> = sringbuf::RingBuffer::new();
+    /// ring_buffer.extend([1, 2, 3]);
+    /// assert_eq!(ring_buffer.read_exact::<2>(), Some([1, 2]));
+    /// assert_eq!(ring_buffer.read_exact::<2>(), None);
+    /// assert_eq!(ring_buffer.read(), Some(3));
+    /// ```
+    pub fn read_exact<const M: usize>(&mut self) -> Option<[T; M]> {
+        if self.len() < M {
+            return None;
+        }
 
-        ring_buffer.write(1);
+        let mut values = [const { MaybeUninit::<T>::uninit() }; M];
+        let dst = values.as_mut_ptr().cast::<T>();
+        let (head, tail) = self.as_slices();
+        let head_count = M.min(head.len());
+        // SAFETY: the oldest `M` elements are moved out of the two runs into the array, which
+        // has room for exactly `M` elements
+        unsafe {
+            ptr::copy_nonoverlapping(head.as_ptr(), dst, head_count);
+            ptr::copy_nonoverlapping(tail.as_ptr(), dst.add(head_count), M - head_count);
+        }
 
-        assert_eq!(ring_buffer.read(), Some(1));
-        assert_eq!(ring_buffer, RingBuffer {
-            contents: [None; 3],
-            read_index: 1,
-            write_index: 1,
-        });
+        self.set_read_index(self.slot_index(M));
+        self.set_len(self.len() - M);
+        // SAFETY: every element of the array was initialized above
+        Some(unsafe { ptr::read(values.as_ptr().cast::<[T; M]>()) })
     }
 
-    #[test]
-    fn read_empty() {
-        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+    /// Discards up to `count` of the oldest elements of a ring buffer, returning the number
+    /// discarded
+    ///
+    /// The read cursor moves in one step; the elements are dropped in place, which costs nothing
+    /// for types without a destructor
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 5> = sringbuf::RingBuffer::new();
+    /// ring_buffer.extend([1, 2, 3, 4]);
+    /// assert_eq!(ring_buffer.advance_read(3), 3);
+    /// assert_eq!(ring_buffer.read(), Some(4));
+    /// assert_eq!(ring_buffer.advance_read(2), 0);
+    /// ```
+    pub fn advance_read(&mut self, count: usize) -> usize {
+        let count = count.min(self.len());
+        let read_index = C::from_usize(self.slot_index(count));
+        let len = C::from_usize(self.len() - count);
 
-        assert_eq!(ring_buffer.read(), None);
-        assert_eq!(ring_buffer, RingBuffer {
-            contents: [None; 3],
-            read_index: 0,
-            write_index: 0,
-        });
+        let (head, tail) = self.as_mut_slices();
+        let head_count = count.min(head.len());
+        let (head, tail): (*mut [T], *mut [T]) =
+            (&mut head[..head_count], &mut tail[..count - head_count]);
+
+        self.read_index = read_index;
+        self.len = len;
+
+        // SAFETY: the slices held the oldest `count` elements, which are no longer reachable now
+        // that the read cursor has moved past them
+        unsafe {
+            ptr::drop_in_place(head);
+            ptr::drop_in_place(tail);
+        }
+        count
     }
 
-    fn read_wrap_around() {
-        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+    /// Keeps the oldest `len` elements of a ring buffer, dropping the newer ones
+    ///
+    /// Does nothing if the ring buffer holds no more than `len` elements
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 5> = sringbuf::RingBuffer::new();
+    /// ring_buffer.extend([1, 2, 3, 4]);
+    /// ring_buffer.truncate(2);
+    /// assert_eq!(ring_buffer.as_slices(), (&[1, 2][..], &[][..]));
+    /// ```
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len() {
+            return;
+        }
 
-        ring_buffer.write(1);
-        ring_buffer.write(2);
-        ring_buffer.write(3);
-        ring_buffer.write(4);
-        ring_buffer.write(5);
-        ring_buffer.write(6);
+        let (head, tail) = self.as_mut_slices();
+        let (head, tail): (*mut [T], *mut [T]) = if len < head.len() {
+            (&mut head[len..], tail)
+        } else {
+            let tail_len = len - head.len();
+            (&mut [], &mut tail[tail_len..])
+        };
 
-        ring_buffer.read();
+        self.len = C::from_usize(len);
 
-        assert_eq!(ring_buffer.read(), Some(5));
-        assert_eq!(ring_buffer, RingBuffer {
-            contents: [None, None, Some(6)],
+        // SAFETY: the slices held the newest elements, which are no longer reachable now that
+        // the length excludes them
+        unsafe {
+            ptr::drop_in_place(head);
+            ptr::drop_in_place(tail);
+        }
+    }
+
+    /// Keeps only the elements for which `f` returns `true`, dropping the others and keeping
+    /// the survivors in FIFO order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 4> = sringbuf::RingBuffer::new();
+    /// ring_buffer.extend([1, 2, 3, 4, 5]);
+    /// ring_buffer.retain(|data| data % 2 == 1);
+    /// assert!(ring_buffer.iter().eq(&[3, 5]));
+    /// ```
+    pub fn retain(&mut self, mut f: impl FnMut(&T) -> bool) {
+        self.retain_mut(|data| f(data));
+    }
+
+    /// Keeps only the elements for which `f` returns `true`, like [`retain`](Self::retain), but
+    /// lets `f` modify the elements it visits
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 4> = sringbuf::RingBuffer::new();
+    /// ring_buffer.extend([1, 2, 3, 4]);
+    /// ring_buffer.retain_mut(|data| {
+    ///     *data *= 10;
+    ///     *data > 20
+    /// });
+    /// assert!(ring_buffer.iter().eq(&[30, 40]));
+    /// ```
+    pub fn retain_mut(&mut self, mut f: impl FnMut(&mut T) -> bool) {
+        let len = self.len();
+        let mut kept = 0;
+        for index in 0..len {
+            let slot = self.slot_index(index);
+            // SAFETY: `index` is less than `len`, so its slot holds a live element
+            if f(unsafe { self.slots_mut()[slot].assume_init_mut() }) {
+                let kept_slot = self.slot_index(kept);
+                self.slots_mut().swap(kept_slot, slot);
+                kept += 1;
+            }
+        }
+        self.truncate(kept);
+    }
+
+    /// Rotates the elements of a ring buffer `n` places to the left, so the element at logical
+    /// position `n` becomes the oldest and the `n` oldest elements become the newest
+    ///
+    /// A full ring buffer only moves its read cursor; otherwise at most half of the elements are
+    /// moved
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is more than the length
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 5> = sringbuf::RingBuffer::new();
+    /// ring_buffer.extend([1, 2, 3, 4]);
+    /// ring_buffer.rotate_left(1);
+    /// assert!(ring_buffer.iter().eq(&[2, 3, 4, 1]));
+    /// ```
+    pub fn rotate_left(&mut self, n: usize) {
+        assert!(n <= self.len(), "rotation out of bounds");
+
+        if self.is_full() {
+            self.set_read_index(self.slot_index(n));
+        } else if n <= self.len() / 2 {
+            for _ in 0..n {
+                let data = self.read().unwrap();
+                let write_index = self.write_index();
+                self.slots_mut()[write_index] = MaybeUninit::new(data);
+                self.set_len(self.len() + 1);
+            }
+        } else {
+            for _ in n..self.len() {
+                let data = self.pop_back().unwrap();
+                let read_index = self.slot_index(self.capacity() - 1);
+                self.slots_mut()[read_index] = MaybeUninit::new(data);
+                self.set_read_index(read_index);
+                self.set_len(self.len() + 1);
+            }
+        }
+    }
+
+    /// Rotates the elements of a ring buffer `n` places to the right, so the `n` newest elements
+    /// become the oldest
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is more than the length
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 5> = sringbuf::RingBuffer::new();
+    /// ring_buffer.extend([1, 2, 3, 4]);
+    /// ring_buffer.rotate_right(1);
+    /// assert!(ring_buffer.iter().eq(&[4, 1, 2, 3]));
+    /// ```
+    pub fn rotate_right(&mut self, n: usize) {
+        assert!(n <= self.len(), "rotation out of bounds");
+
+        self.rotate_left(self.len() - n);
+    }
+
+    /// Replaces the contents of a ring buffer with clones of `value`, leaving it full
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut delay_line: sringbuf::RingBuffer<f32, 4> = sringbuf::RingBuffer::new();
+    /// delay_line.fill(0.0);
+    /// assert!(delay_line.is_full());
+    /// assert_eq!(delay_line.write(0.5), Some(0.0));
+    /// ```
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        self.fill_with(|| value.clone());
+    }
+
+    /// Replaces the contents of a ring buffer with values returned by calling `f` repeatedly,
+    /// oldest first, leaving it full
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 3> = sringbuf::RingBuffer::new();
+    /// ring_buffer.write(9);
+    /// let mut next = 0;
+    /// ring_buffer.fill_with(|| {
+    ///     next += 1;
+    ///     next
+    /// });
+    /// assert!(ring_buffer.iter().eq(&[1, 2, 3]));
+    /// ```
+    pub fn fill_with(&mut self, mut f: impl FnMut() -> T) {
+        self.clear();
+        for slot in 0..self.capacity() {
+            self.slots_mut()[slot] = MaybeUninit::new(f());
+            self.set_len(slot + 1);
+        }
+    }
+
+    /// Removes all elements from a ring buffer, dropping them, and resets it to its initial
+    /// state
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 5> = sringbuf::RingBuffer::new();
+    /// ring_buffer.write(1);
+    /// ring_buffer.write(2);
+    /// ring_buffer.clear();
+    /// assert!(ring_buffer.is_empty());
+    /// assert_eq!(ring_buffer.read(), None);
+    /// ```
+    pub fn clear(&mut self) {
+        let (head, tail) = self.as_mut_slices();
+        let (head, tail): (*mut [T], *mut [T]) = (head, tail);
+
+        self.read_index = C::ZERO;
+        self.len = C::ZERO;
+
+        // SAFETY: the slices held the live elements, which are no longer reachable now that the
+        // buffer is empty. If a destructor panics the remaining elements are leaked, not dropped
+        // twice
+        unsafe {
+            ptr::drop_in_place(head);
+            ptr::drop_in_place(tail);
+        }
+    }
+
+    /// Removes all elements from a ring buffer, returning them from oldest to newest as an
+    /// iterator
+    ///
+    /// If the iterator is dropped before it is fully consumed, the remaining elements are removed
+    /// as well
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 3> = sringbuf::RingBuffer::new();
+    /// ring_buffer.write(1);
+    /// ring_buffer.write(2);
+    /// let batch: Vec<u8> = ring_buffer.drain().collect();
+    /// assert_eq!(batch, [1, 2]);
+    /// assert!(ring_buffer.is_empty());
+    /// ```
+    pub fn drain(&mut self) -> Drain<'_, T, S, P, C> {
+        Drain::new(self)
+    }
+
+    /// Removes the oldest elements of a ring buffer as long as `predicate` returns `true` for
+    /// them, returning them from oldest to newest as an iterator
+    ///
+    /// The first element for which `predicate` returns `false` is left in the ring buffer, along
+    /// with everything after it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u32, 4> = sringbuf::RingBuffer::new();
+    /// ring_buffer.extend([10, 20, 30, 40]);
+    /// let expired: Vec<u32> = ring_buffer.read_while(|timestamp| *timestamp < 25).collect();
+    /// assert_eq!(expired, [10, 20]);
+    /// assert_eq!(ring_buffer.peek(), Some(&30));
+    /// ```
+    pub fn read_while<F>(&mut self, predicate: F) -> ReadWhile<'_, T, S, F, P, C>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        ReadWhile::new(self, predicate)
+    }
+
+    /// Removes the elements for which `predicate` returns `true`, returning them from oldest to
+    /// newest as an iterator
+    ///
+    /// The other elements stay in the ring buffer in FIFO order. `predicate` may modify the
+    /// elements it visits, and elements left unvisited when the iterator is dropped are kept
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 4> = sringbuf::RingBuffer::new();
+    /// ring_buffer.extend([1, 2, 3, 4, 5]);
+    /// let even: Vec<u8> = ring_buffer.extract_if(|data| *data % 2 == 0).collect();
+    /// assert_eq!(even, [2, 4]);
+    /// assert!(ring_buffer.iter().eq(&[3, 5]));
+    /// ```
+    pub fn extract_if<F>(&mut self, predicate: F) -> ExtractIf<'_, T, S, F, P, C>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        ExtractIf::new(self, predicate)
+    }
+
+    /// Returns a reference to the oldest available element of a ring buffer without removing it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 5> = sringbuf::RingBuffer::new();
+    /// ring_buffer.write(1);
+    /// ring_buffer.write(2);
+    /// assert_eq!(ring_buffer.peek(), Some(&1));
+    /// assert_eq!(ring_buffer.read(), Some(1));
+    /// ```
+    pub fn peek(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns a mutable reference to the oldest available element of a ring buffer without
+    /// removing it, so it can be updated in place before it is read
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<(u8, u8), 5> = sringbuf::RingBuffer::new();
+    /// ring_buffer.write((1, 0));
+    /// ring_buffer.write((2, 0));
+    /// if let Some((_, retries)) = ring_buffer.peek_mut() {
+    ///     *retries += 1;
+    /// }
+    /// assert_eq!(ring_buffer.read(), Some((1, 1)));
+    /// ```
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    /// Returns an iterator over the oldest `n` elements of a ring buffer, or all of them if it
+    /// holds fewer, without consuming them
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 8> = sringbuf::RingBuffer::new();
+    /// ring_buffer.extend([3, b'a', b'b']);
+    /// let frame_len = usize::from(ring_buffer[0]);
+    /// assert_eq!(ring_buffer.peek_n(frame_len + 1).len(), 3);
+    /// ring_buffer.write(b'c');
+    /// assert!(ring_buffer.peek_n(frame_len + 1).eq(&[3, b'a', b'b', b'c']));
+    /// ```
+    pub fn peek_n(&self, n: usize) -> Iter<'_, T> {
+        let (head, tail) = self.as_slices();
+        if n <= head.len() {
+            Iter::new(&head[..n], &[])
+        } else {
+            Iter::new(head, &tail[..n.min(self.len()) - head.len()])
+        }
+    }
+
+    /// Returns a reference to the most recently written element of a ring buffer
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 5> = sringbuf::RingBuffer::new();
+    /// ring_buffer.write(1);
+    /// ring_buffer.write(2);
+    /// assert_eq!(ring_buffer.last(), Some(&2));
+    /// ```
+    pub fn last(&self) -> Option<&T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        // SAFETY: the buffer is not empty, so the last occupied slot holds the newest element
+        Some(unsafe { self.slots()[self.last_index()].assume_init_ref() })
+    }
+
+    /// Returns a mutable reference to the most recently written element of a ring buffer
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 5> = sringbuf::RingBuffer::new();
+    /// ring_buffer.write(1);
+    /// if let Some(last) = ring_buffer.last_mut() {
+    ///     *last = 3;
+    /// }
+    /// assert_eq!(ring_buffer.read(), Some(3));
+    /// ```
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let index = self.last_index();
+        // SAFETY: the buffer is not empty, so the last occupied slot holds the newest element
+        Some(unsafe { self.slots_mut()[index].assume_init_mut() })
+    }
+
+    /// Writes a value as the newest element, the same as [`RingBuffer::write`]
+    ///
+    /// This and [`RingBuffer::pop_front`], [`RingBuffer::front`] and [`RingBuffer::back`] share
+    /// their names with the `VecDeque` methods, so code written for a deque ports over by
+    /// changing its type. Unlike a deque, a full ring buffer applies its overflow policy
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 2> = sringbuf::RingBuffer::new();
+    /// ring_buffer.push_back(1);
+    /// ring_buffer.push_back(2);
+    /// assert_eq!(ring_buffer.front(), Some(&1));
+    /// assert_eq!(ring_buffer.back(), Some(&2));
+    /// assert_eq!(ring_buffer.pop_front(), Some(1));
+    /// ```
+    pub fn push_back(&mut self, data: T) -> Option<T>
+    where
+        P: OverflowPolicy,
+    {
+        self.write(data)
+    }
+
+    /// Removes and returns the oldest element, the same as [`RingBuffer::read`]
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.read()
+    }
+
+    /// Returns a reference to the oldest element, the same as [`RingBuffer::peek`]
+    pub fn front(&self) -> Option<&T> {
+        self.peek()
+    }
+
+    /// Returns a mutable reference to the oldest element, the same as [`RingBuffer::peek_mut`]
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.peek_mut()
+    }
+
+    /// Returns a reference to the newest element, the same as [`RingBuffer::last`]
+    pub fn back(&self) -> Option<&T> {
+        self.last()
+    }
+
+    /// Returns a mutable reference to the newest element, the same as [`RingBuffer::last_mut`]
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.last_mut()
+    }
+
+    /// Writes a value as the oldest element, so it is read next
+    ///
+    /// If the ring buffer is full the [`policy`] applies at the other end: by default the newest
+    /// element is removed to make room, [`policy::Reject`] discards `data` and
+    /// [`policy::Panic`] panics. The element that did not make it into the ring buffer is
+    /// returned
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 3> = sringbuf::RingBuffer::new();
+    /// ring_buffer.extend([2, 3, 4]);
+    /// assert_eq!(ring_buffer.push_front(1), Some(4));
+    /// assert_eq!(ring_buffer.as_slices(), (&[1][..], &[2, 3][..]));
+    /// ```
+    pub fn push_front(&mut self, data: T) -> Option<T>
+    where
+        P: OverflowPolicy,
+    {
+        let mut displaced = None;
+        if self.is_full() {
+            match P::OVERFLOW {
+                Overflow::Overwrite => displaced = self.pop_back(),
+                Overflow::Reject => return Some(data),
+                Overflow::Panic => panic!("write to a full ring buffer"),
+            }
+        }
+
+        let read_index = self.slot_index(self.capacity() - 1);
+        self.slots_mut()[read_index] = MaybeUninit::new(data);
+        self.set_read_index(read_index);
+        self.set_len(self.len() + 1);
+        displaced
+    }
+
+    /// Removes and returns the newest element, or `None` if the ring buffer is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 3> = sringbuf::RingBuffer::new();
+    /// ring_buffer.extend([1, 2]);
+    /// assert_eq!(ring_buffer.pop_back(), Some(2));
+    /// assert_eq!(ring_buffer.pop_back(), Some(1));
+    /// assert_eq!(ring_buffer.pop_back(), None);
+    /// ```
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let index = self.last_index();
+        self.set_len(self.len() - 1);
+        // SAFETY: the slot held the newest element, which is now outside the occupied range
+        Some(unsafe { self.slots()[index].assume_init_read() })
+    }
+
+    /// Removes and returns the newest element, discarding every older one, or `None` if the
+    /// ring buffer is empty
+    ///
+    /// This suits consumers that only care about the latest value. To take the newest element
+    /// and keep the rest, use [`RingBuffer::pop_back`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 4> = sringbuf::RingBuffer::new();
+    /// ring_buffer.extend([1, 2, 3]);
+    /// assert_eq!(ring_buffer.read_newest(), Some(3));
+    /// assert!(ring_buffer.is_empty());
+    /// ```
+    pub fn read_newest(&mut self) -> Option<T> {
+        let newest = self.pop_back();
+        self.clear();
+        newest
+    }
+
+    /// Returns a reference to the element at logical position `index`, where `0` is the oldest
+    /// available element, or `None` if `index` is out of bounds
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 3> = sringbuf::RingBuffer::new();
+    /// ring_buffer.write(1);
+    /// ring_buffer.write(2);
+    /// assert_eq!(ring_buffer.get(1), Some(&2));
+    /// assert_eq!(ring_buffer.get(2), None);
+    /// ```
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len() {
+            return None;
+        }
+
+        // SAFETY: `index` is less than `len`, so its slot holds a live element
+        Some(unsafe { self.slots()[self.slot_index(index)].assume_init_ref() })
+    }
+
+    /// Returns a mutable reference to the element at logical position `index`, where `0` is the
+    /// oldest available element, or `None` if `index` is out of bounds
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 3> = sringbuf::RingBuffer::new();
+    /// ring_buffer.write(1);
+    /// ring_buffer.write(2);
+    /// if let Some(data) = ring_buffer.get_mut(0) {
+    ///     *data = 5;
+    /// }
+    /// assert_eq!(ring_buffer.read(), Some(5));
+    /// ```
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let slot = self.slot_index(index);
+        // SAFETY: `index` is less than `len`, so its slot holds a live element
+        Some(unsafe { self.slots_mut()[slot].assume_init_mut() })
+    }
+
+    /// Returns `true` if a ring buffer holds an element equal to `x`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 3> = sringbuf::RingBuffer::new();
+    /// ring_buffer.extend([1, 2, 3, 4]);
+    /// assert!(ring_buffer.contains(&4));
+    /// assert!(!ring_buffer.contains(&1));
+    /// ```
+    pub fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        let (head, tail) = self.as_slices();
+        head.contains(x) || tail.contains(x)
+    }
+
+    /// Returns the logical position of the oldest element for which `predicate` returns `true`,
+    /// where `0` is the oldest available element
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 3> = sringbuf::RingBuffer::new();
+    /// ring_buffer.extend([1, 2, 3, 4]);
+    /// assert_eq!(ring_buffer.position(|data| data % 2 == 0), Some(0));
+    /// assert_eq!(ring_buffer.position(|data| *data > 4), None);
+    /// ```
+    pub fn position(&self, predicate: impl FnMut(&T) -> bool) -> Option<usize> {
+        self.iter().position(predicate)
+    }
+
+    /// Returns an iterator over the elements of a ring buffer, from oldest to newest, without
+    /// consuming them
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 3> = sringbuf::RingBuffer::new();
+    /// ring_buffer.write(1);
+    /// ring_buffer.write(2);
+    /// let sum: u8 = ring_buffer.iter().sum();
+    /// assert_eq!(sum, 3);
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (head, tail) = self.as_slices();
+        Iter::new(head, tail)
+    }
+
+    /// Returns an iterator over mutable references to the elements of a ring buffer, from oldest
+    /// to newest, without consuming them
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 3> = sringbuf::RingBuffer::new();
+    /// ring_buffer.write(1);
+    /// ring_buffer.write(2);
+    /// for data in ring_buffer.iter_mut() {
+    ///     *data *= 2;
+    /// }
+    /// assert_eq!(ring_buffer.read(), Some(2));
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (head, tail) = self.as_mut_slices();
+        IterMut::new(head, tail)
+    }
+
+    /// Returns the elements of a ring buffer, from oldest to newest, as a pair of contiguous
+    /// slices
+    ///
+    /// The second slice is empty unless the stored elements wrap around the end of the
+    /// underlying storage
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 3> = sringbuf::RingBuffer::new();
+    /// ring_buffer.write(1);
+    /// ring_buffer.write(2);
+    /// ring_buffer.read();
+    /// ring_buffer.write(3);
+    /// ring_buffer.write(4);
+    /// assert_eq!(ring_buffer.as_slices(), (&[2, 3][..], &[4][..]));
+    /// ```
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let (head, tail) = self.occupied_slots();
+        // SAFETY: occupied slots always hold live elements
+        unsafe { (slice_assume_init_ref(head), slice_assume_init_ref(tail)) }
+    }
+
+    /// Returns the elements of a ring buffer, from oldest to newest, as a pair of contiguous
+    /// mutable slices
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 3> = sringbuf::RingBuffer::new();
+    /// ring_buffer.write(1);
+    /// ring_buffer.write(2);
+    /// let (head, _tail) = ring_buffer.as_mut_slices();
+    /// head[0] = 3;
+    /// assert_eq!(ring_buffer.read(), Some(3));
+    /// ```
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let (head, tail) = self.occupied_slots_mut();
+        // SAFETY: occupied slots always hold live elements
+        unsafe { (slice_assume_init_mut(head), slice_assume_init_mut(tail)) }
+    }
+
+    /// Clones the elements of a ring buffer, from oldest to newest, into a new vector
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 3> = sringbuf::RingBuffer::new();
+    /// ring_buffer.extend([1, 2, 3, 4]);
+    /// assert_eq!(ring_buffer.to_vec(), [2, 3, 4]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        let (head, tail) = self.as_slices();
+        let mut vec = Vec::with_capacity(self.len());
+        vec.extend_from_slice(head);
+        vec.extend_from_slice(tail);
+        vec
+    }
+
+    /// Rearranges the storage of a ring buffer so that its elements are contiguous, returning
+    /// them from oldest to newest as a single mutable slice
+    ///
+    /// The storage is only moved if the elements currently wrap around its end
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 3> = sringbuf::RingBuffer::new();
+    /// ring_buffer.write(1);
+    /// ring_buffer.write(2);
+    /// ring_buffer.read();
+    /// ring_buffer.write(3);
+    /// ring_buffer.write(4);
+    /// assert_eq!(ring_buffer.make_contiguous(), &[2, 3, 4]);
+    /// assert_eq!(ring_buffer.as_slices(), (&[2, 3, 4][..], &[][..]));
+    /// ```
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        if self.read_index() + self.len() > self.capacity() {
+            let read_index = self.read_index();
+            self.slots_mut().rotate_left(read_index);
+            self.set_read_index(0);
+        }
+
+        self.as_mut_slices().0
+    }
+
+    /// Consumes a ring buffer, returning its storage with the elements moved to the front in
+    /// FIFO order, together with the number of elements
+    ///
+    /// The first `len` slots of the returned storage hold the elements, oldest first, and the
+    /// rest are uninitialized. Nothing is dropped, so the caller takes over the elements. A
+    /// [`RingBufferSlice`] gives back the initialized slice it borrowed instead, with its
+    /// original lifetime, so the memory stays usable as plain values
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 4> = sringbuf::RingBuffer::new();
+    /// ring_buffer.extend([1, 2, 3, 4, 5]);
+    ///
+    /// let (slots, len) = ring_buffer.into_inner();
+    /// assert_eq!(len, 4);
+    /// assert_eq!(slots.map(|slot| unsafe { slot.assume_init() }), [2, 3, 4, 5]);
+    ///
+    /// let mut memory = [0u8; 3];
+    /// let mut ring_buffer: sringbuf::RingBufferSlice<u8> =
+    ///     sringbuf::RingBufferSlice::from_slice(&mut memory);
+    /// ring_buffer.extend([1, 2, 3, 4]);
+    /// let (slice, len) = ring_buffer.into_inner();
+    /// slice[0] += 10;
+    /// assert_eq!((memory, len), ([12, 3, 4], 3));
+    /// ```
+    pub fn into_inner(self) -> (S, usize) {
+        let mut ring_buffer = mem::ManuallyDrop::new(self);
+        let read_index = ring_buffer.read_index();
+        ring_buffer.slots_mut().rotate_left(read_index);
+
+        // SAFETY: the ring buffer is never used or dropped again, so its storage is moved out
+        // exactly once
+        let contents = unsafe { ptr::read(&ring_buffer.contents) };
+        (contents, ring_buffer.len())
+    }
+
+    /// Returns the number of elements currently stored in a ring buffer
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 5> = sringbuf::RingBuffer::new();
+    /// ring_buffer.write(1);
+    /// ring_buffer.write(2);
+    /// assert_eq!(ring_buffer.len(), 2);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.len.to_usize()
+    }
+
+    /// Returns `true` if a ring buffer contains no elements
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 5> = sringbuf::RingBuffer::new();
+    /// assert!(ring_buffer.is_empty());
+    /// ring_buffer.write(1);
+    /// assert!(!ring_buffer.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if a ring buffer holds as many elements as its capacity, meaning the next
+    /// write will overwrite unread data
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 2> = sringbuf::RingBuffer::new();
+    /// ring_buffer.write(1);
+    /// ring_buffer.write(2);
+    /// assert!(ring_buffer.is_full());
+    /// ```
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// Returns the occupied slots of a ring buffer, from oldest to newest, as up to two
+    /// contiguous slices
+    fn occupied_slots(&self) -> (&[MaybeUninit<T>], &[MaybeUninit<T>]) {
+        let (read_index, len) = (self.read_index(), self.len());
+        let slots = self.slots();
+        if read_index + len <= slots.len() {
+            return (&slots[read_index..read_index + len], &[]);
+        }
+
+        let (tail, head) = slots.split_at(read_index);
+        (head, &tail[..len - head.len()])
+    }
+
+    /// Returns the occupied slots of a ring buffer, from oldest to newest, as up to two
+    /// contiguous mutable slices
+    fn occupied_slots_mut(&mut self) -> (&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) {
+        let (read_index, len) = (self.read_index(), self.len());
+        let slots = self.slots_mut();
+        if read_index + len <= slots.len() {
+            return (&mut slots[read_index..read_index + len], &mut []);
+        }
+
+        let tail_len = len - (slots.len() - read_index);
+        let (tail, head) = slots.split_at_mut(read_index);
+        (head, &mut tail[..tail_len])
+    }
+
+    /// Moves the elements from logical position `at` onward into the empty ring buffer `other`,
+    /// which must have room for all of them
+    fn split_off_into<S2, P2, C2>(
+        &mut self,
+        at: usize,
+        other: &mut GenericRingBuffer<T, S2, P2, C2>,
+    ) where
+        S2: Storage<T>,
+        C2: Cursor,
+    {
+        assert!(at <= self.len(), "split index out of bounds");
+        debug_assert!(other.is_empty() && other.capacity() >= self.len() - at);
+
+        let count = self.len() - at;
+        let (head, tail) = self.as_slices();
+        let (head, tail): (&[T], &[T]) = if at < head.len() {
+            (&head[at..], tail)
+        } else {
+            (&[], &tail[at - head.len()..])
+        };
+
+        let dst = other.slots_mut().as_mut_ptr().cast::<T>();
+        // SAFETY: the elements are moved into the first slots of `other`, which is empty with
+        // its read cursor at the first slot, then dropped from this ring buffer without running
+        // their destructors
+        unsafe {
+            ptr::copy_nonoverlapping(head.as_ptr(), dst, head.len());
+            ptr::copy_nonoverlapping(tail.as_ptr(), dst.add(head.len()), tail.len());
+        }
+
+        other.set_read_index(0);
+        other.set_len(count);
+        self.set_len(at);
+    }
+
+    /// Returns the free slots of a ring buffer, in write order, as up to two contiguous mutable
+    /// slices
+    fn free_slots_mut(&mut self) -> (&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) {
+        let remaining = self.remaining();
+        let write_index = self.write_index();
+        let slots = self.slots_mut();
+        if write_index + remaining <= slots.len() {
+            return (&mut slots[write_index..write_index + remaining], &mut []);
+        }
+
+        let tail_len = remaining - (slots.len() - write_index);
+        let (tail, head) = slots.split_at_mut(write_index);
+        (head, &mut tail[..tail_len])
+    }
+
+    /// Returns a reference to the element held in slot `slot`, or `None` if the slot is free
+    fn slot(&self, slot: usize) -> Option<&T> {
+        let offset = if slot >= self.read_index() {
+            slot - self.read_index()
+        } else {
+            slot + self.capacity() - self.read_index()
+        };
+
+        self.get(offset)
+    }
+
+    /// Maps a logical position, counted from the oldest element, to its slot index
+    ///
+    /// `read_index` stays below the capacity and `index` never exceeds it, so a single
+    /// conditional subtraction wraps the sum without a division. When the capacity is a power
+    /// of two the sum is masked instead; for inline arrays the capacity is a constant, so the
+    /// choice is made at compile time
+    fn slot_index(&self, index: usize) -> usize {
+        let capacity = self.capacity();
+        let slot = self.read_index() + index;
+        if capacity.is_power_of_two() {
+            slot & (capacity - 1)
+        } else if slot >= capacity {
+            slot - capacity
+        } else {
+            slot
+        }
+    }
+
+    /// Returns the slot index of the oldest element
+    fn read_index(&self) -> usize {
+        self.read_index.to_usize()
+    }
+
+    fn set_read_index(&mut self, read_index: usize) {
+        self.read_index = C::from_usize(read_index);
+    }
+
+    fn set_len(&mut self, len: usize) {
+        self.len = C::from_usize(len);
+    }
+
+    /// Returns the slot index the next element will be written to
+    fn write_index(&self) -> usize {
+        self.slot_index(self.len())
+    }
+
+    /// Returns the slot index of the most recently written element, which must exist
+    fn last_index(&self) -> usize {
+        self.slot_index(self.len() - 1)
+    }
+
+    /// Returns every slot of the underlying storage
+    fn slots(&self) -> &[MaybeUninit<T>] {
+        self.contents.as_slice()
+    }
+
+    /// Returns every slot of the underlying storage mutably
+    fn slots_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        self.contents.as_mut_slice()
+    }
+
+    /// Clones the elements of a ring buffer into the empty `contents`, which must have the same
+    /// capacity, keeping the cursors of the original
+    fn clone_into_storage(&self, contents: S) -> GenericRingBuffer<T, S, P, C>
+    where
+        T: Clone,
+    {
+        let mut ring_buffer = GenericRingBuffer {
+            contents,
+            read_index: self.read_index,
+            len: C::ZERO,
+            marker: PhantomData,
+        };
+
+        for data in self.iter() {
+            let slot = ring_buffer.write_index();
+            ring_buffer.slots_mut()[slot] = MaybeUninit::new(data.clone());
+            ring_buffer.set_len(ring_buffer.len() + 1);
+        }
+
+        ring_buffer
+    }
+
+    /// Returns the maximum number of elements a ring buffer can hold
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let ring_buffer: sringbuf::RingBuffer<u8, 5> = sringbuf::RingBuffer::new();
+    /// assert_eq!(ring_buffer.capacity(), 5);
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.slots().len()
+    }
+
+    /// Returns the number of elements that can be written before a ring buffer is full
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 5> = sringbuf::RingBuffer::new();
+    /// ring_buffer.write(1);
+    /// assert_eq!(ring_buffer.remaining(), 4);
+    /// ```
+    pub fn remaining(&self) -> usize {
+        self.capacity() - self.len()
+    }
+}
+
+impl<T, S, P, C> Drop for GenericRingBuffer<T, S, P, C>
+where
+    S: Storage<T>,
+    C: Cursor,
+{
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T, const N: usize, P, C: Cursor> Clone for RingBuffer<T, N, P, C>
+where
+    T: Clone,
+{
+    /// Clones a ring buffer element by element, keeping the cursors of the original
+    fn clone(&self) -> RingBuffer<T, N, P, C> {
+        self.clone_into_storage([const { MaybeUninit::uninit() }; N])
+    }
+}
+
+impl<T, S, P, C> fmt::Debug for GenericRingBuffer<T, S, P, C>
+where
+    T: fmt::Debug,
+    S: Storage<T>,
+    C: Cursor,
+{
+    /// Prints the elements from oldest to newest with the occupancy and cursors, and with
+    /// `{:#?}` also the slots in storage order, `None` marking the free ones
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 3> = sringbuf::RingBuffer::new();
+    /// ring_buffer.extend([1, 2, 3, 4]);
+    /// assert_eq!(
+    ///     format!("{ring_buffer:?}"),
+    ///     "RingBuffer { contents: [2, 3, 4], len: 3, capacity: 3, read_index: 1, write_index: 1 }"
+    /// );
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let contents = (0..self.len()).map(|index| &self[index]);
+        let alternate = f.alternate();
+
+        let mut debug = f.debug_struct(S::NAME);
+        debug
+            .field("contents", &DebugList(contents))
+            .field("len", &self.len())
+            .field("capacity", &self.capacity())
+            .field("read_index", &self.read_index())
+            .field("write_index", &self.write_index());
+        if alternate {
+            let slots = (0..self.capacity()).map(|slot| self.slot(slot));
+            debug.field("slots", &DebugList(slots));
+        }
+        debug.finish()
+    }
+}
+
+impl<T, S, P, C> fmt::Display for GenericRingBuffer<T, S, P, C>
+where
+    T: fmt::Display,
+    S: Storage<T>,
+    C: Cursor,
+{
+    /// Prints the elements from oldest to newest as a bracketed list, formatting each with the
+    /// flags given
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<f32, 3> = sringbuf::RingBuffer::new();
+    /// ring_buffer.extend([1.0, 2.5, 3.0, 4.25]);
+    /// assert_eq!(format!("{ring_buffer:.1}"), "[2.5, 3.0, 4.2]");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+        for (index, data) in self.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            fmt::Display::fmt(data, f)?;
+        }
+        f.write_str("]")
+    }
+}
+
+impl<T, S, P, C> PartialEq for GenericRingBuffer<T, S, P, C>
+where
+    T: PartialEq,
+    S: Storage<T>,
+    C: Cursor,
+{
+    /// Two ring buffers are equal if they hold equal elements in the same order from oldest to
+    /// newest, whatever slots the elements occupy
+    fn eq(&self, other: &GenericRingBuffer<T, S, P, C>) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq, S: Storage<T>, P, C: Cursor> Eq for GenericRingBuffer<T, S, P, C> {}
+
+impl<T, S, P, C> PartialOrd for GenericRingBuffer<T, S, P, C>
+where
+    T: PartialOrd,
+    S: Storage<T>,
+    C: Cursor,
+{
+    /// Compares the elements from oldest to newest lexicographically
+    fn partial_cmp(&self, other: &GenericRingBuffer<T, S, P, C>) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T, S, P, C> Ord for GenericRingBuffer<T, S, P, C>
+where
+    T: Ord,
+    S: Storage<T>,
+    C: Cursor,
+{
+    /// Compares the elements from oldest to newest lexicographically
+    fn cmp(&self, other: &GenericRingBuffer<T, S, P, C>) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T, S, P, C> Hash for GenericRingBuffer<T, S, P, C>
+where
+    T: Hash,
+    S: Storage<T>,
+    C: Cursor,
+{
+    /// Hashes the length and the elements from oldest to newest, so ring buffers holding the
+    /// same sequence hash the same whatever slots it occupies
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        for data in self.iter() {
+            data.hash(state);
+        }
+    }
+}
+
+impl<T, const N: usize, P, C: Cursor> Default for RingBuffer<T, N, P, C> {
+    /// Creates an empty ring buffer, equivalent to [`RingBuffer::new`]
+    fn default() -> RingBuffer<T, N, P, C> {
+        RingBuffer::new()
+    }
+}
+
+impl<T, const N: usize, P, C: Cursor> From<[T; N]> for RingBuffer<T, N, P, C> {
+    /// Creates a full ring buffer holding `values`, the first one being the oldest
+    fn from(values: [T; N]) -> RingBuffer<T, N, P, C> {
+        let mut ring_buffer = RingBuffer::new();
+        ring_buffer.contents = values.map(MaybeUninit::new);
+        ring_buffer.set_len(N);
+        ring_buffer
+    }
+}
+
+impl<T, const N: usize, P, C: Cursor> TryFrom<&[T]> for RingBuffer<T, N, P, C>
+where
+    T: Clone,
+{
+    type Error = CapacityError;
+
+    /// Creates a ring buffer holding clones of `values`, the first one being the oldest, or
+    /// fails if there are more than `N`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let ring_buffer = sringbuf::RingBuffer::<u8, 3>::try_from(&[1, 2][..]).unwrap();
+    /// assert_eq!(ring_buffer.len(), 2);
+    ///
+    /// let error = sringbuf::RingBuffer::<u8, 3>::try_from(&[1, 2, 3, 4][..]).unwrap_err();
+    /// assert_eq!(error, sringbuf::CapacityError { len: 4, capacity: 3 });
+    /// ```
+    fn try_from(values: &[T]) -> Result<RingBuffer<T, N, P, C>, CapacityError> {
+        if values.len() > N {
+            return Err(CapacityError {
+                len: values.len(),
+                capacity: N,
+            });
+        }
+
+        let mut ring_buffer = RingBuffer::new();
+        for (slot, data) in values.iter().enumerate() {
+            ring_buffer.slots_mut()[slot] = MaybeUninit::new(data.clone());
+            ring_buffer.set_len(slot + 1);
+        }
+        Ok(ring_buffer)
+    }
+}
+
+impl<T, S, P, C> Index<usize> for GenericRingBuffer<T, S, P, C>
+where
+    S: Storage<T>,
+    C: Cursor,
+{
+    type Output = T;
+
+    /// Returns a reference to the element at logical position `index`, where `0` is the oldest
+    /// available element
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 3> = sringbuf::RingBuffer::new();
+    /// ring_buffer.write(1);
+    /// ring_buffer.write(2);
+    /// assert_eq!(ring_buffer[1], 2);
+    /// ```
+    fn index(&self, index: usize) -> &T {
+        let len = self.len();
+        match self.get(index) {
+            Some(data) => data,
+            None => panic!("index out of bounds: the len is {len} but the index is {index}"),
+        }
+    }
+}
+
+impl<T, S, P, C> IndexMut<usize> for GenericRingBuffer<T, S, P, C>
+where
+    S: Storage<T>,
+    C: Cursor,
+{
+    /// Returns a mutable reference to the element at logical position `index`, where `0` is the
+    /// oldest available element
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 3> = sringbuf::RingBuffer::new();
+    /// ring_buffer.write(1);
+    /// ring_buffer[0] = 2;
+    /// assert_eq!(ring_buffer.read(), Some(2));
+    /// ```
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let len = self.len();
+        match self.get_mut(index) {
+            Some(data) => data,
+            None => panic!("index out of bounds: the len is {len} but the index is {index}"),
+        }
+    }
+}
+
+impl<T, S, P, C> Extend<T> for GenericRingBuffer<T, S, P, C>
+where
+    S: Storage<T>,
+    C: Cursor,
+    P: OverflowPolicy,
+{
+    /// Writes every element of an iterator to a ring buffer, in order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 3> = sringbuf::RingBuffer::new();
+    /// ring_buffer.extend([1, 2]);
+    /// assert_eq!(ring_buffer.read(), Some(1));
+    /// assert_eq!(ring_buffer.read(), Some(2));
+    /// ```
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for data in iter {
+            self.write(data);
+        }
+    }
+}
+
+impl<'a, T, S, P, C> Extend<&'a T> for GenericRingBuffer<T, S, P, C>
+where
+    T: Copy + 'a,
+    S: Storage<T>,
+    C: Cursor,
+    P: OverflowPolicy,
+{
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T, const N: usize, P, C: Cursor> FromIterator<T> for RingBuffer<T, N, P, C>
+where
+    P: OverflowPolicy,
+{
+    /// Creates a ring buffer from an iterator, keeping the last `N` elements if the iterator
+    /// yields more than `N`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 3> = (1..=5).collect();
+    /// assert_eq!(ring_buffer.read(), Some(3));
+    /// assert_eq!(ring_buffer.read(), Some(4));
+    /// assert_eq!(ring_buffer.read(), Some(5));
+    /// ```
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> RingBuffer<T, N, P, C> {
+        let mut ring_buffer = RingBuffer::new();
+
+        for data in iter {
+            if ring_buffer.is_full() {
+                ring_buffer.read();
+            }
+
+            ring_buffer.write(data);
+        }
+
+        ring_buffer
+    }
+}
+
+/// Formats the items of an iterator as a list without collecting them first
+struct DebugList<I>(I);
+
+impl<I> fmt::Debug for DebugList<I>
+where
+    I: Iterator + Clone,
+    I::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.0.clone()).finish()
+    }
+}
+
+/// Copies `src` into the uninitialized slots `dst`, which must have the same length
+fn write_slice<T: Copy>(dst: &mut [MaybeUninit<T>], src: &[T]) {
+    // SAFETY: `MaybeUninit<T>` has the same layout as `T`, and every value written is initialized
+    let dst = unsafe { &mut *(dst as *mut [MaybeUninit<T>] as *mut [T]) };
+    dst.copy_from_slice(src);
+}
+
+/// Reinterprets a slice of initialized slots as a slice of elements
+///
+/// # Safety
+///
+/// Every slot in `slots` must hold an initialized value
+unsafe fn slice_assume_init_ref<T>(slots: &[MaybeUninit<T>]) -> &[T] {
+    // SAFETY: `MaybeUninit<T>` has the same layout as `T`, and the caller guarantees that every
+    // slot is initialized
+    unsafe { &*(slots as *const [MaybeUninit<T>] as *const [T]) }
+}
+
+/// Reinterprets a mutable slice of initialized slots as a mutable slice of elements
+///
+/// # Safety
+///
+/// Every slot in `slots` must hold an initialized value
+unsafe fn slice_assume_init_mut<T>(slots: &mut [MaybeUninit<T>]) -> &mut [T] {
+    // SAFETY: `MaybeUninit<T>` has the same layout as `T`, and the caller guarantees that every
+    // slot is initialized
+    unsafe { &mut *(slots as *mut [MaybeUninit<T>] as *mut [T]) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::rc::Rc;
+
+    #[derive(Debug, PartialEq)]
+    struct Snapshot<T, const N: usize> {
+        contents: [Option<T>; N],
+        read_index: usize,
+        write_index: usize,
+        len: usize,
+    }
+
+    fn snapshot<T: Clone, const N: usize, P>(ring_buffer: &RingBuffer<T, N, P>) -> Snapshot<T, N> {
+        Snapshot {
+            contents: core::array::from_fn(|slot| ring_buffer.slot(slot).cloned()),
+            read_index: ring_buffer.read_index(),
+            write_index: ring_buffer.write_index(),
+            len: ring_buffer.len(),
+        }
+    }
+
+    #[test]
+    fn new_valid_inputs() {
+        let ring_buffer: RingBuffer<char, 5> = RingBuffer::new();
+
+        assert_eq!(snapshot(&ring_buffer), Snapshot {
+            contents: [None; 5],
+            read_index: 0,
+            write_index: 0,
+            len: 0,
+        });
+    }
+
+    #[test]
+    fn new_with_const() {
+        const RING_BUFFER: RingBuffer<char, 4> = RingBuffer::new_with(['a', 'b', 'c']);
+
+        assert_eq!(snapshot(&RING_BUFFER), Snapshot {
+            contents: [Some('a'), Some('b'), Some('c'), None],
+            read_index: 0,
+            write_index: 3,
+            len: 3,
+        });
+    }
+
+    #[test]
+    fn new_with_drops_elements() {
+        let counter = Rc::new(());
+        let ring_buffer: RingBuffer<Rc<()>, 3> =
+            RingBuffer::new_with([Rc::clone(&counter), Rc::clone(&counter)]);
+        assert_eq!(Rc::strong_count(&counter), 3);
+
+        drop(ring_buffer);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn from_array_full() {
+        let mut ring_buffer: RingBuffer<char, 3> = RingBuffer::from(['a', 'b', 'c']);
+
+        assert_eq!(snapshot(&ring_buffer), Snapshot {
+            contents: [Some('a'), Some('b'), Some('c')],
+            read_index: 0,
+            write_index: 0,
+            len: 3,
+        });
+        assert_eq!(ring_buffer.write('d'), Some('a'));
+        assert_eq!(ring_buffer, RingBuffer::new_from_array(['b', 'c', 'd']));
+    }
+
+    #[test]
+    fn try_from_slice() {
+        let ring_buffer = RingBuffer::<String, 3>::try_from(&["a".to_string()][..]).unwrap();
+        assert!(ring_buffer.iter().eq(["a"]));
+
+        let full = RingBuffer::<u8, 2>::try_from(&[1, 2][..]).unwrap();
+        assert!(full.is_full());
+
+        let error = RingBuffer::<u8, 2>::try_from(&[1, 2, 3][..]).unwrap_err();
+        assert_eq!(error.to_string(), "3 elements do not fit in a ring buffer of capacity 2");
+    }
+
+    #[test]
+    fn from_slice_newest_keeps_last() {
+        let ring_buffer = RingBuffer::<String, 2>::from_slice_newest(&["a".to_string()]);
+        assert!(ring_buffer.iter().eq(["a"]));
+
+        let ring_buffer = RingBuffer::<u8, 2>::from_slice_newest(&[1, 2, 3]);
+        assert!(ring_buffer.is_full());
+        assert!(ring_buffer.iter().eq(&[2, 3]));
+        assert!(RingBuffer::<u8, 2>::from_slice_newest(&[]).is_empty());
+    }
+
+    #[test]
+    fn into_inner_linearizes() {
+        let counter = Rc::new(());
+        let mut ring_buffer: RingBuffer<Rc<()>, 3> = RingBuffer::new();
+        ring_buffer.extend(std::iter::repeat_with(|| Rc::clone(&counter)).take(2));
+        ring_buffer.read();
+        ring_buffer.extend([Rc::clone(&counter), Rc::new(())]);
+
+        let (mut slots, len) = ring_buffer.into_inner();
+        assert_eq!(len, 3);
+        assert_eq!(Rc::strong_count(&counter), 3);
+        // SAFETY: the first `len` slots hold the elements, each dropped once by the loop
+        assert_eq!(Rc::strong_count(unsafe { slots[2].assume_init_ref() }), 1);
+        for slot in &mut slots[..len] {
+            unsafe { slot.assume_init_drop() };
+        }
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn to_vec_wrapped() {
+        let mut ring_buffer: RingBuffer<String, 3> = RingBuffer::new();
+        assert!(ring_buffer.to_vec().is_empty());
+
+        ring_buffer.extend(["a", "b", "c", "d"].map(String::from));
+        assert_eq!(ring_buffer.as_slices().1, ["d"]);
+        assert_eq!(ring_buffer.to_vec(), ["b", "c", "d"]);
+    }
+
+    #[test]
+    fn deque_aliases() {
+        let mut ring_buffer: RingBuffer<char, 2> = RingBuffer::new();
+        assert_eq!(ring_buffer.front(), None);
+        assert_eq!(ring_buffer.back(), None);
+
+        ring_buffer.push_back('a');
+        ring_buffer.push_back('b');
+        assert_eq!(ring_buffer.push_back('c'), Some('a'));
+        *ring_buffer.back_mut().unwrap() = 'd';
+
+        assert_eq!(ring_buffer.front(), Some(&'b'));
+        assert_eq!(ring_buffer.back(), Some(&'d'));
+        assert_eq!(ring_buffer.pop_front(), Some('b'));
+        assert_eq!(ring_buffer.pop_front(), Some('d'));
+        assert_eq!(ring_buffer.pop_front(), None);
+    }
+
+    #[test]
+    fn push_front_pop_back() {
+        let mut ring_buffer: RingBuffer<char, 3> = RingBuffer::new();
+        assert_eq!(ring_buffer.push_front('b'), None);
+        assert_eq!(ring_buffer.push_front('a'), None);
+        ring_buffer.write('c');
+
+        assert_eq!(snapshot(&ring_buffer), Snapshot {
+            contents: [Some('c'), Some('a'), Some('b')],
+            read_index: 1,
+            write_index: 1,
+            len: 3,
+        });
+        assert_eq!(ring_buffer.push_front('z'), Some('c'));
+        assert!(ring_buffer.iter().eq(&['z', 'a', 'b']));
+        assert_eq!(ring_buffer.pop_back(), Some('b'));
+        assert_eq!(ring_buffer.read(), Some('z'));
+        assert_eq!(ring_buffer.pop_back(), Some('a'));
+        assert_eq!(ring_buffer.pop_back(), None);
+    }
+
+    #[test]
+    fn push_front_reject_policy() {
+        let mut ring_buffer: RingBuffer<u8, 1, policy::Reject> = RingBuffer::new();
+        ring_buffer.write(1);
+        assert_eq!(ring_buffer.push_front(0), Some(0));
+        assert_eq!(ring_buffer.read(), Some(1));
+    }
+
+    #[test]
+    fn read_newest_discards_stale() {
+        let counter = Rc::new(());
+        let mut ring_buffer: RingBuffer<Rc<()>, 3> = RingBuffer::new();
+        ring_buffer.extend(std::iter::repeat_with(|| Rc::clone(&counter)).take(4));
+        ring_buffer.write(Rc::new(()));
+
+        let newest = ring_buffer.read_newest().unwrap();
+        assert_eq!(Rc::strong_count(&newest), 1);
+        assert_eq!(Rc::strong_count(&counter), 1);
+        assert!(ring_buffer.is_empty());
+        assert!(ring_buffer.read_newest().is_none());
+    }
+
+    #[test]
+    fn advance_read_wrap_around() {
+        let counter = Rc::new(());
+        let mut ring_buffer: RingBuffer<Rc<()>, 4> = RingBuffer::new();
+        ring_buffer.extend(std::iter::repeat_with(|| Rc::clone(&counter)).take(6));
+        ring_buffer.write(Rc::new(()));
+        assert_eq!(Rc::strong_count(&counter), 4);
+
+        assert_eq!(ring_buffer.advance_read(3), 3);
+        assert_eq!(Rc::strong_count(&counter), 1);
+        assert_eq!(ring_buffer.len(), 1);
+        assert_eq!(Rc::strong_count(ring_buffer.peek().unwrap()), 1);
+
+        assert_eq!(ring_buffer.advance_read(5), 1);
+        assert!(ring_buffer.is_empty());
+        ring_buffer.write(Rc::clone(&counter));
+        assert_eq!(ring_buffer.peek(), Some(&counter));
+    }
+
+    #[test]
+    fn truncate_wrap_around() {
+        let mut ring_buffer: RingBuffer<String, 4> = RingBuffer::new();
+        ring_buffer.extend(["a", "b", "c", "d", "e", "f"].map(String::from));
+
+        ring_buffer.truncate(5);
+        assert_eq!(ring_buffer.len(), 4);
+        ring_buffer.truncate(3);
+        assert!(ring_buffer.iter().eq(["c", "d", "e"]));
+        ring_buffer.truncate(1);
+        assert!(ring_buffer.iter().eq(["c"]));
+
+        ring_buffer.write("g".to_string());
+        ring_buffer.truncate(0);
+        assert!(ring_buffer.is_empty());
+    }
+
+    #[test]
+    fn retain_wrap_around() {
+        let mut ring_buffer: RingBuffer<String, 4> = RingBuffer::new();
+        ring_buffer.extend(["a", "bb", "c", "dd", "e", "ff"].map(String::from));
+
+        ring_buffer.retain(|data| data.len() == 2);
+        assert!(ring_buffer.iter().eq(["dd", "ff"]));
+        ring_buffer.write("g".to_string());
+        assert!(ring_buffer.iter().eq(["dd", "ff", "g"]));
+        ring_buffer.retain(|_| false);
+        assert!(ring_buffer.is_empty());
+    }
+
+    #[test]
+    fn retain_mut_updates_survivors() {
+        let mut ring_buffer: RingBuffer<u32, 3> = RingBuffer::new();
+        ring_buffer.extend([1, 2, 3, 4]);
+
+        ring_buffer.retain_mut(|data| {
+            *data += 1;
+            *data != 4
+        });
+        assert!(ring_buffer.iter().eq(&[3, 5]));
+        assert_eq!(snapshot(&ring_buffer).write_index, 0);
+    }
+
+    #[test]
+    fn contains_position_wrap_around() {
+        let mut ring_buffer: RingBuffer<u8, 4> = RingBuffer::new();
+        ring_buffer.extend([1, 2, 3, 4, 5, 6]);
+        assert_eq!(ring_buffer.as_slices(), (&[3, 4][..], &[5, 6][..]));
+
+        assert!(ring_buffer.contains(&3));
+        assert!(ring_buffer.contains(&6));
+        assert!(!ring_buffer.contains(&2));
+        assert_eq!(ring_buffer.position(|data| *data == 5), Some(2));
+        assert_eq!(ring_buffer[2], 5);
+        assert_eq!(ring_buffer.position(|data| *data == 1), None);
+    }
+
+    #[test]
+    fn peek_n_wrap_around() {
+        let mut ring_buffer: RingBuffer<u8, 4> = RingBuffer::new();
+        ring_buffer.extend([1, 2, 3, 4, 5, 6]);
+
+        assert_eq!(ring_buffer.peek_n(0).next(), None);
+        assert!(ring_buffer.peek_n(1).eq(&[3]));
+        assert!(ring_buffer.peek_n(3).eq(&[3, 4, 5]));
+        assert!(ring_buffer.peek_n(3).rev().eq(&[5, 4, 3]));
+        assert!(ring_buffer.peek_n(10).eq(&[3, 4, 5, 6]));
+        assert_eq!(ring_buffer.len(), 4);
+    }
+
+    #[test]
+    fn read_exact_wrap_around() {
+        let mut ring_buffer: RingBuffer<String, 4> = RingBuffer::new();
+        ring_buffer.extend(["a", "b", "c", "d", "e"].map(String::from));
+
+        assert_eq!(ring_buffer.read_exact::<5>(), None);
+        assert_eq!(ring_buffer.len(), 4);
+        assert_eq!(ring_buffer.read_exact::<0>(), Some([]));
+        assert_eq!(ring_buffer.read_exact::<3>(), Some(["b", "c", "d"].map(String::from)));
+        assert_eq!(snapshot(&ring_buffer), Snapshot {
+            contents: [Some("e".to_string()), None, None, None],
+            read_index: 0,
+            write_index: 1,
+            len: 1,
+        });
+    }
+
+    #[test]
+    fn write_all_or_nothing_wrap_around() {
+        let mut ring_buffer: RingBuffer<u8, 4> = RingBuffer::new();
+        ring_buffer.extend([1, 2, 3]);
+        ring_buffer.advance_read(2);
+
+        assert_eq!(ring_buffer.write_all_or_nothing(&[4, 5, 6, 7]), Err(Full(&[4, 5, 6, 7][..])));
+        assert_eq!(ring_buffer.len(), 1);
+        assert_eq!(ring_buffer.write_all_or_nothing(&[4, 5, 6]), Ok(()));
+        assert!(ring_buffer.iter().eq(&[3, 4, 5, 6]));
+        assert_eq!(ring_buffer.write_all_or_nothing(&[]), Ok(()));
+        assert!(ring_buffer.write_all_or_nothing(&[7]).is_err());
+    }
+
+    #[test]
+    fn write_with_policies() {
+        let mut ring_buffer: RingBuffer<String, 2> = RingBuffer::new();
+        ring_buffer.extend(["a", "b"].map(String::from));
+
+        // SAFETY: the closure initializes the slot
+        let evicted = unsafe {
+            ring_buffer.write_with(|slot| {
+                slot.write("c".to_string());
+            })
+        };
+        assert_eq!(evicted.unwrap().as_deref(), Some("a"));
+        assert!(ring_buffer.iter().eq(["b", "c"]));
+
+        let mut ring_buffer: RingBuffer<String, 1, policy::Reject> = RingBuffer::new();
+        ring_buffer.write("a".to_string());
+        // SAFETY: the closure is never called
+        let evicted = unsafe { ring_buffer.write_with(|_| unreachable!()) };
+        assert_eq!(evicted, Err(Full(())));
+        assert!(ring_buffer.iter().eq(["a"]));
+    }
+
+    #[test]
+    fn write_with_panicking_init() {
+        let mut ring_buffer: RingBuffer<String, 2> = RingBuffer::new();
+        ring_buffer.extend(["a", "b", "c"].map(String::from));
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            // SAFETY: the closure panics without returning
+            let _ = unsafe { ring_buffer.write_with(|_| panic!("init failed")) };
+        }));
+        assert!(result.is_err());
+        assert!(ring_buffer.iter().eq(["b", "c"]));
+    }
+
+    #[test]
+    fn write_default_policies() {
+        let mut ring_buffer: RingBuffer<u32, 2> = RingBuffer::new();
+        ring_buffer.extend([1, 2]);
+
+        let (last, evicted) = ring_buffer.write_default().unwrap();
+        *last = 3;
+        assert_eq!(evicted, Some(1));
+        assert!(ring_buffer.iter().eq(&[2, 3]));
+
+        let mut ring_buffer: RingBuffer<u32, 1, policy::Reject> = RingBuffer::new();
+        assert_eq!(ring_buffer.write_default().map(|(last, _)| *last), Ok(0));
+        assert_eq!(ring_buffer.write_default(), Err(Full(())));
+    }
+
+    #[test]
+    fn peek_mut_wrap_around() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+        assert_eq!(ring_buffer.peek_mut(), None);
+
+        ring_buffer.extend([1, 2, 3, 4]);
+        *ring_buffer.peek_mut().unwrap() = 5;
+        *ring_buffer.front_mut().unwrap() += 1;
+        assert!(ring_buffer.iter().eq(&[6, 3, 4]));
+        assert_eq!(snapshot(&ring_buffer).contents, [Some(4), Some(6), Some(3)]);
+    }
+
+    #[test]
+    fn append_wrap_around() {
+        let mut ring_buffer: RingBuffer<String, 4> = RingBuffer::new();
+        let mut other: RingBuffer<String, 3> = RingBuffer::new();
+        ring_buffer.extend(["a", "b", "c", "d", "e"].map(String::from));
+        other.extend(["f", "g", "h", "i"].map(String::from));
+
+        ring_buffer.advance_read(2);
+        ring_buffer.append(&mut other);
+        assert!(ring_buffer.iter().eq(["e", "g", "h", "i"]));
+        assert!(other.is_empty());
+
+        other.extend(["j", "k", "l"].map(String::from));
+        ring_buffer.append(&mut other);
+        assert!(ring_buffer.iter().eq(["i", "j", "k", "l"]));
+
+        let mut large: RingBuffer<String, 6> = RingBuffer::new();
+        large.extend(["m", "n", "o", "p", "q", "r"].map(String::from));
+        ring_buffer.append(&mut large);
+        assert!(ring_buffer.iter().eq(["o", "p", "q", "r"]));
+        assert!(large.is_empty());
+    }
+
+    #[test]
+    fn append_reject_policy() {
+        let mut ring_buffer: RingBuffer<u8, 3, policy::Reject> = RingBuffer::new();
+        let mut other: RingBuffer<u8, 3> = RingBuffer::new();
+        ring_buffer.write(1);
+        other.extend([2, 3, 4]);
+
+        ring_buffer.append(&mut other);
+        assert!(ring_buffer.iter().eq(&[1, 2, 3]));
+        assert!(other.iter().eq(&[4]));
+    }
+
+    #[test]
+    fn split_off_wrap_around() {
+        let mut ring_buffer: RingBuffer<String, 4> = RingBuffer::new();
+        ring_buffer.extend(["a", "b", "c", "d", "e", "f"].map(String::from));
+
+        let mut newer = ring_buffer.split_off(3);
+        assert!(ring_buffer.iter().eq(["c", "d", "e"]));
+        assert!(newer.iter().eq(["f"]));
+        newer.extend(["g", "h", "i", "j"].map(String::from));
+        assert!(newer.iter().eq(["g", "h", "i", "j"]));
+
+        let newer = ring_buffer.split_off(1);
+        assert!(ring_buffer.iter().eq(["c"]));
+        assert!(newer.iter().eq(["d", "e"]));
+        assert!(ring_buffer.split_off(1).is_empty());
+        assert!(ring_buffer.split_off(0).iter().eq(["c"]));
+        assert!(ring_buffer.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "split index out of bounds")]
+    fn split_off_out_of_bounds() {
+        let mut ring_buffer: RingBuffer<u8, 4> = RingBuffer::new();
+        ring_buffer.write(1);
+        ring_buffer.split_off(2);
+    }
+
+    #[test]
+    fn rotate_partial_and_full() {
+        let mut ring_buffer: RingBuffer<String, 5> = RingBuffer::new();
+        ring_buffer.extend(["a", "b", "c", "d"].map(String::from));
+
+        ring_buffer.rotate_left(3);
+        assert!(ring_buffer.iter().eq(["d", "a", "b", "c"]));
+        ring_buffer.rotate_right(3);
+        assert!(ring_buffer.iter().eq(["a", "b", "c", "d"]));
+        ring_buffer.rotate_left(4);
+        assert!(ring_buffer.iter().eq(["a", "b", "c", "d"]));
+
+        ring_buffer.write("e".to_string());
+        ring_buffer.rotate_right(2);
+        assert!(ring_buffer.iter().eq(["d", "e", "a", "b", "c"]));
+        assert_eq!(snapshot(&ring_buffer).read_index, 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "rotation out of bounds")]
+    fn rotate_out_of_bounds() {
+        let mut ring_buffer: RingBuffer<u8, 4> = RingBuffer::new();
+        ring_buffer.write(1);
+        ring_buffer.rotate_left(2);
+    }
+
+    #[test]
+    fn fill_replaces_contents() {
+        let counter = Rc::new(());
+        let mut ring_buffer: RingBuffer<Rc<()>, 3> = RingBuffer::new();
+        ring_buffer.extend([Rc::new(()), Rc::new(())]);
+        ring_buffer.read();
+
+        ring_buffer.fill(Rc::clone(&counter));
+        assert!(ring_buffer.is_full());
+        assert_eq!(Rc::strong_count(&counter), 4);
+        assert_eq!(snapshot(&ring_buffer).read_index, 0);
+
+        ring_buffer.fill_with(|| Rc::new(()));
+        assert_eq!(Rc::strong_count(&counter), 1);
+        assert_eq!(ring_buffer.len(), 3);
+    }
+
+    #[test]
+    fn write_coalesce_repeated_keys() {
+        fn repeat(last: &mut (char, u32), data: (char, u32)) -> Result<(), (char, u32)> {
+            if last.0 != data.0 {
+                return Err(data);
+            }
+            last.1 += data.1;
+            Ok(())
+        }
+
+        let mut ring_buffer: RingBuffer<(char, u32), 2> = RingBuffer::new();
+        assert_eq!(ring_buffer.write_coalesce(('a', 1), repeat), None);
+        assert_eq!(ring_buffer.write_coalesce(('a', 1), repeat), None);
+        assert_eq!(ring_buffer.write_coalesce(('b', 1), repeat), None);
+        assert_eq!(ring_buffer.write_coalesce(('b', 2), repeat), None);
+        assert_eq!(ring_buffer.write_coalesce(('c', 1), repeat), Some(('a', 2)));
+        assert!(ring_buffer.iter().eq(&[('b', 3), ('c', 1)]));
+    }
+
+    #[test]
+    fn write_valid_inputs_first() {
+        let mut ring_buffer: RingBuffer<char, 5> = RingBuffer::new();
+
+        ring_buffer.write('a');
+        
+        assert_eq!(snapshot(&ring_buffer), Snapshot {
+            contents: [Some('a'), None, None, None, None],
+            read_index: 0,
+            write_index: 1,
+            len: 1,
+        });
+    }
+
+    #[test]
+    fn write_wrap_around() {
+        let mut ring_buffer: RingBuffer<u8, 5> = RingBuffer::new();
+
+        ring_buffer.write(1);
+        ring_buffer.write(2);
+        ring_buffer.write(3);
+        ring_buffer.write(4);
+        ring_buffer.write(5);
+        ring_buffer.write(6);
+        ring_buffer.write(7);
+
+        assert_eq!(snapshot(&ring_buffer), Snapshot {
+            contents: [Some(6), Some(7), Some(3), Some(4), Some(5)],
+            read_index: 2,
+            write_index: 2,
+            len: 5,
+        });
+    }
+
+    #[test]
+    fn read_first() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+
+        ring_buffer.write(1);
+
+        assert_eq!(ring_buffer.read(), Some(1));
+        assert_eq!(snapshot(&ring_buffer), Snapshot {
+            contents: [None; 3],
+            read_index: 1,
+            write_index: 1,
+            len: 0,
+        });
+    }
+
+    #[test]
+    fn read_empty() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+
+        assert_eq!(ring_buffer.read(), None);
+        assert_eq!(snapshot(&ring_buffer), Snapshot {
+            contents: [None; 3],
+            read_index: 0,
+            write_index: 0,
+            len: 0,
+        });
+    }
+
+    #[test]
+    fn read_wrap_around() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+
+        ring_buffer.write(1);
+        ring_buffer.write(2);
+        ring_buffer.write(3);
+        ring_buffer.write(4);
+        ring_buffer.write(5);
+        ring_buffer.write(6);
+
+        ring_buffer.read();
+
+        assert_eq!(ring_buffer.read(), Some(5));
+        assert_eq!(snapshot(&ring_buffer), Snapshot {
+            contents: [None, None, Some(6)],
+            read_index: 2,
+            write_index: 0,
+            len: 1,
+        });
+    }
+
+    #[test]
+    fn len_tracks_writes_and_reads() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+
+        assert_eq!(ring_buffer.len(), 0);
+        assert!(ring_buffer.is_empty());
+        assert_eq!(ring_buffer.remaining(), 3);
+
+        ring_buffer.write(1);
+        ring_buffer.write(2);
+        assert_eq!(ring_buffer.len(), 2);
+        assert_eq!(ring_buffer.remaining(), 1);
+
+        ring_buffer.read();
+        assert_eq!(ring_buffer.len(), 1);
+        assert!(!ring_buffer.is_empty());
+        assert!(!ring_buffer.is_full());
+    }
+
+    #[test]
+    fn len_saturates_at_capacity() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+
+        for i in 0..7 {
+            ring_buffer.write(i);
+        }
+
+        assert_eq!(ring_buffer.len(), 3);
+        assert_eq!(ring_buffer.capacity(), 3);
+        assert_eq!(ring_buffer.remaining(), 0);
+        assert!(ring_buffer.is_full());
+    }
+
+    #[test]
+    fn peek_does_not_consume() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+
+        ring_buffer.write(1);
+        ring_buffer.write(2);
+
+        assert_eq!(ring_buffer.peek(), Some(&1));
+        assert_eq!(ring_buffer.peek(), Some(&1));
+        assert_eq!(ring_buffer.len(), 2);
+        assert_eq!(ring_buffer.read(), Some(1));
+        assert_eq!(ring_buffer.peek(), Some(&2));
+    }
+
+    #[test]
+    fn peek_empty() {
+        let ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+
+        assert_eq!(ring_buffer.peek(), None);
+    }
+
+    #[test]
+    fn last_wrap_around() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+
+        assert_eq!(ring_buffer.last(), None);
+
+        ring_buffer.write(1);
+        ring_buffer.write(2);
+        ring_buffer.write(3);
+        assert_eq!(ring_buffer.last(), Some(&3));
+
+        ring_buffer.write(4);
+        assert_eq!(ring_buffer.last(), Some(&4));
+    }
+
+    #[test]
+    fn last_mut_modifies_newest() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+
+        ring_buffer.write(1);
+        ring_buffer.write(2);
+        *ring_buffer.last_mut().unwrap() = 5;
+
+        assert_eq!(ring_buffer.read(), Some(1));
+        assert_eq!(ring_buffer.read(), Some(5));
+        assert_eq!(ring_buffer.last_mut(), None);
+    }
+
+    #[test]
+    fn get_wrap_around() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+
+        ring_buffer.write(1);
+        ring_buffer.write(2);
+        ring_buffer.write(3);
+        ring_buffer.read();
+        ring_buffer.write(4);
+
+        assert_eq!(ring_buffer.get(0), Some(&2));
+        assert_eq!(ring_buffer.get(1), Some(&3));
+        assert_eq!(ring_buffer.get(2), Some(&4));
+        assert_eq!(ring_buffer.get(3), None);
+    }
+
+    #[test]
+    fn get_mut_out_of_bounds() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+
+        ring_buffer.write(1);
+
+        assert_eq!(ring_buffer.get_mut(1), None);
+        *ring_buffer.get_mut(0).unwrap() = 2;
+        assert_eq!(ring_buffer.peek(), Some(&2));
+    }
+
+    #[test]
+    fn index_logical_order() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+
+        ring_buffer.write(1);
+        ring_buffer.write(2);
+        ring_buffer.read();
+        ring_buffer.write(3);
+        ring_buffer.write(4);
+        ring_buffer[2] = 5;
+
+        assert_eq!(ring_buffer[0], 2);
+        assert_eq!(ring_buffer[1], 3);
+        assert_eq!(ring_buffer[2], 5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+
+        ring_buffer.write(1);
+
+        let _data = ring_buffer[1];
+    }
+
+    #[test]
+    fn clear_resets_indices() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+
+        ring_buffer.write(1);
+        ring_buffer.write(2);
+        ring_buffer.read();
+        ring_buffer.clear();
+
+        assert_eq!(ring_buffer, RingBuffer::new());
+
+        ring_buffer.write(3);
+        assert_eq!(ring_buffer.read(), Some(3));
+    }
+
+    #[test]
+    fn extend_wrap_around() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+
+        ring_buffer.write(1);
+        ring_buffer.read();
+        ring_buffer.extend(&[2, 3, 4]);
+
+        assert_eq!(ring_buffer.len(), 3);
+        assert_eq!(ring_buffer.read(), Some(2));
+        assert_eq!(ring_buffer.read(), Some(3));
+        assert_eq!(ring_buffer.read(), Some(4));
+    }
+
+    #[test]
+    fn from_iter_keeps_last() {
+        let ring_buffer: RingBuffer<u8, 3> = (1..=7).collect();
+
+        assert_eq!(ring_buffer.len(), 3);
+        assert_eq!(ring_buffer.iter().copied().collect::<Vec<_>>(), [5, 6, 7]);
+    }
+
+    #[test]
+    fn from_iter_partial() {
+        let ring_buffer: RingBuffer<u8, 3> = [1, 2].into_iter().collect();
+
+        assert_eq!(ring_buffer.len(), 2);
+        assert_eq!(ring_buffer.peek(), Some(&1));
+        assert_eq!(ring_buffer.last(), Some(&2));
+    }
+
+    #[test]
+    fn default_is_new() {
+        let ring_buffer: RingBuffer<u8, 3> = RingBuffer::default();
+
+        assert_eq!(ring_buffer, RingBuffer::new());
+    }
+
+    #[test]
+    fn write_from_wrap_around() {
+        let mut ring_buffer: RingBuffer<u8, 4> = RingBuffer::new();
+
+        ring_buffer.write(1);
+        ring_buffer.write(2);
+        ring_buffer.write(3);
+        ring_buffer.read();
+        ring_buffer.read();
+
+        assert_eq!(ring_buffer.write_from(&[4, 5, 6, 7]), 3);
+        assert_eq!(snapshot(&ring_buffer), Snapshot {
+            contents: [Some(5), Some(6), Some(3), Some(4)],
             read_index: 2,
+            write_index: 2,
+            len: 4,
+        });
+        assert_eq!(ring_buffer.write_from(&[8]), 0);
+    }
+
+    #[test]
+    fn write_from_partial() {
+        let mut ring_buffer: RingBuffer<u8, 4> = RingBuffer::new();
+
+        assert_eq!(ring_buffer.write_from(&[1, 2]), 2);
+        assert_eq!(snapshot(&ring_buffer), Snapshot {
+            contents: [Some(1), Some(2), None, None],
+            read_index: 0,
+            write_index: 2,
+            len: 2,
+        });
+    }
+
+    #[test]
+    fn read_into_wrap_around() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+
+        ring_buffer.write(1);
+        ring_buffer.write(2);
+        ring_buffer.read();
+        ring_buffer.write(3);
+        ring_buffer.write(4);
+
+        let mut dst = [0; 2];
+        assert_eq!(ring_buffer.read_into(&mut dst), 2);
+        assert_eq!(dst, [2, 3]);
+        assert_eq!(snapshot(&ring_buffer), Snapshot {
+            contents: [Some(4), None, None],
+            read_index: 0,
+            write_index: 1,
+            len: 1,
+        });
+    }
+
+    #[test]
+    fn read_into_empty() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+        let mut dst = [0; 2];
+
+        assert_eq!(ring_buffer.read_into(&mut dst), 0);
+        assert_eq!(dst, [0, 0]);
+    }
+
+    #[test]
+    fn as_slices_wrap_around() {
+        let mut ring_buffer: RingBuffer<u8, 4> = RingBuffer::new();
+
+        assert_eq!(ring_buffer.as_slices(), (&[][..], &[][..]));
+
+        ring_buffer.write_from(&[1, 2, 3, 4]);
+        ring_buffer.read();
+        ring_buffer.read();
+        ring_buffer.write(5);
+
+        assert_eq!(ring_buffer.as_slices(), (&[3, 4][..], &[5][..]));
+
+        let (head, tail) = ring_buffer.as_mut_slices();
+        head[1] = 6;
+        tail[0] = 7;
+        assert_eq!(ring_buffer.iter().copied().collect::<Vec<_>>(), [3, 6, 7]);
+    }
+
+    #[test]
+    fn as_slices_full() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+
+        ring_buffer.write_from(&[1, 2, 3]);
+
+        assert_eq!(ring_buffer.as_slices(), (&[1, 2, 3][..], &[][..]));
+    }
+
+    #[test]
+    fn make_contiguous_wrap_around() {
+        let mut ring_buffer: RingBuffer<u8, 4> = RingBuffer::new();
+
+        ring_buffer.write_from(&[1, 2, 3]);
+        ring_buffer.read();
+        ring_buffer.read();
+        ring_buffer.write_from(&[4, 5]);
+
+        assert_eq!(ring_buffer.make_contiguous(), &mut [3, 4, 5]);
+        assert_eq!(snapshot(&ring_buffer), Snapshot {
+            contents: [Some(3), Some(4), Some(5), None],
+            read_index: 0,
+            write_index: 3,
+            len: 3,
+        });
+
+        ring_buffer.write(6);
+        assert_eq!(ring_buffer.iter().copied().collect::<Vec<_>>(), [3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn make_contiguous_already_contiguous() {
+        let mut ring_buffer: RingBuffer<u8, 4> = RingBuffer::new();
+
+        ring_buffer.write_from(&[1, 2, 3]);
+        ring_buffer.read();
+
+        assert_eq!(ring_buffer.make_contiguous(), &mut [2, 3]);
+        assert_eq!(ring_buffer.read_index(), 1);
+    }
+
+    #[test]
+    fn try_write_full() {
+        let mut ring_buffer: RingBuffer<u8, 2> = RingBuffer::new();
+
+        assert_eq!(ring_buffer.try_write(1), Ok(()));
+        assert_eq!(ring_buffer.try_write(2), Ok(()));
+        assert_eq!(ring_buffer.try_write(3), Err(Full(3)));
+        assert_eq!(snapshot(&ring_buffer), Snapshot {
+            contents: [Some(1), Some(2)],
+            read_index: 0,
+            write_index: 0,
+            len: 2,
+        });
+
+        ring_buffer.read();
+        assert_eq!(ring_buffer.try_write(3), Ok(()));
+        assert_eq!(ring_buffer.iter().copied().collect::<Vec<_>>(), [2, 3]);
+    }
+
+    #[test]
+    fn write_reject_policy() {
+        let mut ring_buffer: RingBuffer<u8, 2, policy::Reject> = RingBuffer::new();
+
+        ring_buffer.write(1);
+        ring_buffer.write(2);
+        ring_buffer.write(3);
+
+        assert_eq!(snapshot(&ring_buffer), Snapshot {
+            contents: [Some(1), Some(2)],
+            read_index: 0,
             write_index: 0,
+            len: 2,
         });
     }
+
+    #[test]
+    #[should_panic]
+    fn write_panic_policy() {
+        let mut ring_buffer: RingBuffer<u8, 2, policy::Panic> = RingBuffer::new();
+
+        ring_buffer.write(1);
+        ring_buffer.write(2);
+        ring_buffer.write(3);
+    }
+
+    #[test]
+    fn write_returns_evicted() {
+        let mut ring_buffer: RingBuffer<u8, 2> = RingBuffer::new();
+
+        assert_eq!(ring_buffer.write(1), None);
+        assert_eq!(ring_buffer.write(2), None);
+        assert_eq!(ring_buffer.write(3), Some(1));
+        assert_eq!(ring_buffer.write(4), Some(2));
+        assert_eq!(ring_buffer.len(), 2);
+    }
+
+    #[test]
+    fn write_returns_rejected() {
+        let mut ring_buffer: RingBuffer<u8, 1, policy::Reject> = RingBuffer::new();
+
+        assert_eq!(ring_buffer.write(1), None);
+        assert_eq!(ring_buffer.write(2), Some(2));
+        assert_eq!(ring_buffer.read(), Some(1));
+    }
+
+    #[test]
+    fn read_after_overwrite_is_fifo() {
+        let mut ring_buffer: RingBuffer<u8, 5> = RingBuffer::new();
+
+        for i in 1..=7 {
+            ring_buffer.write(i);
+        }
+
+        assert_eq!(ring_buffer.peek(), Some(&3));
+        assert_eq!(ring_buffer.last(), Some(&7));
+        assert_eq!(ring_buffer.iter().copied().collect::<Vec<_>>(), [3, 4, 5, 6, 7]);
+        for i in 3..=7 {
+            assert_eq!(ring_buffer.read(), Some(i));
+        }
+        assert_eq!(ring_buffer.read(), None);
+    }
+
+    #[test]
+    fn write_non_copy() {
+        let mut ring_buffer: RingBuffer<String, 2> = RingBuffer::new();
+
+        ring_buffer.write(String::from("a"));
+        ring_buffer.write(String::from("b"));
+
+        assert_eq!(ring_buffer.write(String::from("c")), Some(String::from("a")));
+        assert_eq!(ring_buffer.peek().map(String::as_str), Some("b"));
+        assert_eq!(ring_buffer.read(), Some(String::from("b")));
+        assert_eq!(ring_buffer.read(), Some(String::from("c")));
+        assert_eq!(ring_buffer.read(), None);
+    }
+
+    #[test]
+    fn clear_drops_elements() {
+        let counter = Rc::new(());
+        let mut ring_buffer: RingBuffer<Rc<()>, 3> = RingBuffer::new();
+
+        ring_buffer.write(Rc::clone(&counter));
+        ring_buffer.write(Rc::clone(&counter));
+        ring_buffer.write(Rc::clone(&counter));
+        ring_buffer.read();
+        ring_buffer.write(Rc::clone(&counter));
+        assert_eq!(Rc::strong_count(&counter), 4);
+
+        ring_buffer.clear();
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn drop_drops_elements() {
+        let counter = Rc::new(());
+        let mut ring_buffer: RingBuffer<Rc<()>, 3> = RingBuffer::new();
+
+        ring_buffer.write(Rc::clone(&counter));
+        ring_buffer.write(Rc::clone(&counter));
+        drop(ring_buffer);
+
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn clone_non_copy() {
+        let mut ring_buffer: RingBuffer<String, 3> = RingBuffer::new();
+
+        ring_buffer.write(String::from("a"));
+        ring_buffer.write(String::from("b"));
+        ring_buffer.read();
+        ring_buffer.write(String::from("c"));
+        ring_buffer.write(String::from("d"));
+
+        let clone = ring_buffer.clone();
+        assert_eq!(clone, ring_buffer);
+        assert_eq!(snapshot(&clone), snapshot(&ring_buffer));
+    }
+
+    #[test]
+    fn debug_alternate_slots() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+        ring_buffer.extend([1, 2, 3]);
+        ring_buffer.read();
+        ring_buffer.read();
+        ring_buffer.write(4);
+
+        let debug = format!("{ring_buffer:?}");
+        assert_eq!(
+            debug,
+            "RingBuffer { contents: [3, 4], len: 2, capacity: 3, read_index: 2, write_index: 1 }"
+        );
+
+        let pretty = format!("{ring_buffer:#?}");
+        assert!(pretty.contains("slots: [\n        Some(\n            4,\n        ),\n        None,"));
+        assert!(pretty.ends_with("Some(\n            3,\n        ),\n    ],\n}"));
+    }
+
+    #[test]
+    fn display_fifo_order() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+        assert_eq!(ring_buffer.to_string(), "[]");
+
+        ring_buffer.extend([1, 2, 3, 4, 5]);
+        assert_eq!(ring_buffer.to_string(), "[3, 4, 5]");
+        assert_eq!(format!("{ring_buffer:>2}"), "[ 3,  4,  5]");
+    }
+
+    #[test]
+    fn eq_ignores_rotation() {
+        let mut rotated: RingBuffer<u8, 4> = RingBuffer::new();
+        rotated.extend([0, 0, 1, 2, 3]);
+        rotated.read();
+        let contiguous: RingBuffer<u8, 4> = [1, 2, 3].into_iter().collect();
+        assert_eq!(rotated, contiguous);
+
+        rotated.write(4);
+        assert_ne!(rotated, contiguous);
+        rotated.clear();
+        assert_eq!(rotated, RingBuffer::new());
+    }
+
+    #[test]
+    fn ord_lexicographic() {
+        let mut rotated: RingBuffer<u8, 3> = RingBuffer::new();
+        rotated.extend([9, 1, 2, 3]);
+        let shorter: RingBuffer<u8, 3> = [1, 2].into_iter().collect();
+        let larger: RingBuffer<u8, 3> = [1, 3].into_iter().collect();
+
+        assert_eq!(rotated.cmp(&shorter), Ordering::Greater);
+        assert_eq!(rotated.cmp(&larger), Ordering::Less);
+        assert_eq!(rotated.cmp(&[1, 2, 3].into_iter().collect()), Ordering::Equal);
+
+        let set: std::collections::BTreeSet<_> = [larger, rotated, shorter].into_iter().collect();
+        assert!(set.iter().map(|ring_buffer| ring_buffer.len()).eq([2, 3, 2]));
+    }
+
+    #[test]
+    fn hash_ignores_rotation() {
+        fn hash<T: Hash>(value: &T) -> u64 {
+            let mut hasher = std::hash::DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let mut rotated: RingBuffer<u8, 4> = RingBuffer::new();
+        rotated.extend([0, 0, 1, 2, 3]);
+        rotated.read();
+        let contiguous: RingBuffer<u8, 4> = [1, 2, 3].into_iter().collect();
+
+        assert_ne!(rotated.as_slices(), contiguous.as_slices());
+        assert_eq!(hash(&rotated), hash(&contiguous));
+        assert_ne!(hash(&rotated), hash(&RingBuffer::<u8, 4>::new()));
+    }
+
+    #[test]
+    fn size_is_storage_plus_cursors() {
+        assert_eq!(
+            mem::size_of::<RingBuffer<u32, 8>>(),
+            8 * mem::size_of::<u32>() + 2 * mem::size_of::<usize>()
+        );
+    }
+
+    #[test]
+    fn cursors_match_model() {
+        let mut ring_buffer: RingBuffer<u32, 7> = RingBuffer::new();
+        let mut model = std::collections::VecDeque::new();
+
+        for i in 0..1000u32 {
+            if i % 3 == 2 {
+                assert_eq!(ring_buffer.read(), model.pop_front());
+            } else {
+                if model.len() == 7 {
+                    model.pop_front();
+                }
+                model.push_back(i);
+                ring_buffer.write(i);
+            }
+
+            assert_eq!(ring_buffer.len(), model.len());
+            assert_eq!(ring_buffer.is_full(), model.len() == 7);
+            assert!(ring_buffer.iter().eq(model.iter()));
+        }
+    }
+
+    #[test]
+    fn power_of_two_wrap_around() {
+        let mut ring_buffer: RingBuffer<u8, 8> = RingBuffer::new();
+
+        for i in 0..13 {
+            ring_buffer.write(i);
+        }
+        ring_buffer.read();
+
+        assert_eq!(ring_buffer.read_index(), 6);
+        assert_eq!(ring_buffer.write_index(), 5);
+        assert_eq!(ring_buffer.iter().copied().collect::<Vec<_>>(), [6, 7, 8, 9, 10, 11, 12]);
+        assert_eq!(ring_buffer.last(), Some(&12));
+    }
 }
@@ -0,0 +1,251 @@
+//! A ring buffer that reports its occupancy and traffic through the `metrics` facade
+//!
+//! Every [`MeteredRingBuffer`] registers its metrics once, labelled with `buffer` set to the
+//! name it was created with, so whichever exporter the application installed, such as
+//! `metrics-exporter-prometheus`, picks them up without further setup:
+//!
+//! | Metric                   | Kind    | Labels                                         |
+//! |--------------------------|---------|------------------------------------------------|
+//! | `sringbuf_occupancy`     | gauge   | `buffer`                                       |
+//! | `sringbuf_writes_total`  | counter | `buffer`                                       |
+//! | `sringbuf_reads_total`   | counter | `buffer`                                       |
+//! | `sringbuf_dropped_total` | counter | `buffer`, `reason` (`overwritten`, `rejected`) |
+//!
+//! Throughput is the rate of the write and read counters
+use core::ops::Deref;
+
+use metrics::{Counter, Gauge, SharedString};
+
+use crate::RingBuffer;
+use crate::policy::{OverflowPolicy, Overwrite};
+use crate::stats::WriteOutcome;
+
+/// A FIFO ring buffer that publishes its occupancy, writes, reads and dropped elements as
+/// metrics under a name of the caller's choosing
+///
+/// The metric handles are registered with the recorder installed when the ring buffer is
+/// created
+#[derive(Clone, Debug)]
+pub struct MeteredRingBuffer<T, const N: usize, P = Overwrite> {
+    ring_buffer: RingBuffer<T, N, P>,
+    occupancy: Gauge,
+    writes: Counter,
+    reads: Counter,
+    overwritten: Counter,
+    rejected: Counter,
+}
+
+impl<T, const N: usize, P> MeteredRingBuffer<T, N, P> {
+    /// Creates a new, empty MeteredRingBuffer instance whose metrics are labelled with `name`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let ring_buffer: sringbuf::MeteredRingBuffer<u8, 16> =
+    ///     sringbuf::MeteredRingBuffer::new("uart_rx");
+    /// assert!(ring_buffer.is_empty());
+    /// ```
+    pub fn new(name: impl Into<SharedString>) -> MeteredRingBuffer<T, N, P> {
+        let name = name.into();
+        let occupancy = metrics::gauge!("sringbuf_occupancy", "buffer" => name.clone());
+        occupancy.set(0.0);
+
+        MeteredRingBuffer {
+            ring_buffer: RingBuffer::new(),
+            occupancy,
+            writes: metrics::counter!("sringbuf_writes_total", "buffer" => name.clone()),
+            reads: metrics::counter!("sringbuf_reads_total", "buffer" => name.clone()),
+            overwritten: metrics::counter!(
+                "sringbuf_dropped_total",
+                "buffer" => name.clone(),
+                "reason" => "overwritten"
+            ),
+            rejected: metrics::counter!(
+                "sringbuf_dropped_total",
+                "buffer" => name,
+                "reason" => "rejected"
+            ),
+        }
+    }
+
+    /// Writes data to a ring buffer, counting the write and what it displaced
+    ///
+    /// The overflow policy `P` applies as for
+    /// [`GenericRingBuffer::write`](crate::GenericRingBuffer::write), and the element that did
+    /// not make it into the ring buffer is returned
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::MeteredRingBuffer<u8, 2> =
+    ///     sringbuf::MeteredRingBuffer::new("samples");
+    /// ring_buffer.extend([1, 2]);
+    /// assert_eq!(ring_buffer.write(3), Some(1));
+    /// ```
+    pub fn write(&mut self, data: T) -> Option<T>
+    where
+        P: OverflowPolicy,
+    {
+        let (displaced, outcome) = WriteOutcome::write(&mut self.ring_buffer, data);
+
+        self.writes.increment(1);
+        match outcome {
+            WriteOutcome::Stored => {}
+            WriteOutcome::Overwritten => self.overwritten.increment(1),
+            WriteOutcome::Rejected => self.rejected.increment(1),
+        }
+        self.record_occupancy();
+        displaced
+    }
+
+    /// Reads the oldest element from a ring buffer, or returns `None` if it is empty
+    pub fn read(&mut self) -> Option<T> {
+        let data = self.ring_buffer.read();
+        if data.is_some() {
+            self.reads.increment(1);
+            self.record_occupancy();
+        }
+        data
+    }
+
+    /// Drops every element of a ring buffer without counting them as read
+    pub fn clear(&mut self) {
+        self.ring_buffer.clear();
+        self.record_occupancy();
+    }
+
+    /// Returns the underlying ring buffer, which stops reporting metrics
+    pub fn into_inner(self) -> RingBuffer<T, N, P> {
+        self.ring_buffer
+    }
+
+    /// Sets the occupancy gauge to the current length
+    fn record_occupancy(&self) {
+        self.occupancy.set(self.ring_buffer.len() as f64);
+    }
+}
+
+impl<T, const N: usize, P> Deref for MeteredRingBuffer<T, N, P> {
+    type Target = RingBuffer<T, N, P>;
+
+    fn deref(&self) -> &RingBuffer<T, N, P> {
+        &self.ring_buffer
+    }
+}
+
+impl<T, const N: usize, P: OverflowPolicy> Extend<T> for MeteredRingBuffer<T, N, P> {
+    /// Writes every element of an iterator to a ring buffer
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for data in iter {
+            self.write(data);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MeteredRingBuffer;
+    use crate::policy::Reject;
+
+    use metrics::{
+        Counter, Gauge, Histogram, Key, KeyName, Label, Metadata, Recorder, SharedString, Unit,
+    };
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    /// A recorder storing every counter and gauge in an atomic, keyed by name and labels
+    #[derive(Default)]
+    struct TestRecorder {
+        metrics: Mutex<Vec<(Key, Arc<AtomicU64>)>>,
+    }
+
+    impl TestRecorder {
+        fn handle(&self, key: &Key) -> Arc<AtomicU64> {
+            let mut metrics = self.metrics.lock().unwrap();
+            if let Some((_, value)) = metrics.iter().find(|(known, _)| known == key) {
+                return value.clone();
+            }
+            let value = Arc::new(AtomicU64::new(0));
+            metrics.push((key.clone(), value.clone()));
+            value
+        }
+
+        fn value(&self, name: &'static str, labels: &[(&'static str, &'static str)]) -> u64 {
+            let labels: Vec<Label> = labels.iter().map(Label::from).collect();
+            self.handle(&Key::from_parts(name, labels))
+                .load(Ordering::Acquire)
+        }
+    }
+
+    impl Recorder for TestRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            Counter::from_arc(self.handle(key))
+        }
+
+        fn register_gauge(&self, key: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::from_arc(self.handle(key))
+        }
+
+        fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::noop()
+        }
+    }
+
+    #[test]
+    fn metrics_overwrite_policy() {
+        let recorder = TestRecorder::default();
+        let mut ring_buffer: MeteredRingBuffer<u8, 3> =
+            metrics::with_local_recorder(&recorder, || MeteredRingBuffer::new("rx"));
+
+        ring_buffer.extend(0..5);
+        ring_buffer.read();
+
+        let buffer = ("buffer", "rx");
+        assert_eq!(recorder.value("sringbuf_writes_total", &[buffer]), 5);
+        assert_eq!(recorder.value("sringbuf_reads_total", &[buffer]), 1);
+        assert_eq!(
+            recorder.value(
+                "sringbuf_dropped_total",
+                &[buffer, ("reason", "overwritten")]
+            ),
+            2
+        );
+        assert_eq!(
+            f64::from_bits(recorder.value("sringbuf_occupancy", &[buffer])),
+            2.0
+        );
+
+        ring_buffer.clear();
+        assert_eq!(recorder.value("sringbuf_occupancy", &[buffer]), 0);
+    }
+
+    #[test]
+    fn metrics_reject_policy() {
+        let recorder = TestRecorder::default();
+        let mut ring_buffer: MeteredRingBuffer<u8, 2, Reject> =
+            metrics::with_local_recorder(&recorder, || MeteredRingBuffer::new("tx"));
+
+        ring_buffer.extend(0..5);
+        assert_eq!(ring_buffer.read(), Some(0));
+        assert_eq!(ring_buffer.read(), Some(1));
+        assert_eq!(ring_buffer.read(), None);
+
+        let buffer = ("buffer", "tx");
+        assert_eq!(recorder.value("sringbuf_reads_total", &[buffer]), 2);
+        assert_eq!(
+            recorder.value("sringbuf_dropped_total", &[buffer, ("reason", "rejected")]),
+            3
+        );
+        assert_eq!(
+            recorder.value(
+                "sringbuf_dropped_total",
+                &[buffer, ("reason", "overwritten")]
+            ),
+            0
+        );
+    }
+}
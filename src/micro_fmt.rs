@@ -0,0 +1,84 @@
+//! [`ufmt`] [`uDebug`] and [`uDisplay`] implementations for targets where `core::fmt` is
+//! too large
+use ufmt::{Formatter, uDebug, uDisplay, uWrite};
+
+use crate::{Cursor, GenericRingBuffer, Storage};
+
+impl<T, S, P, C> uDebug for GenericRingBuffer<T, S, P, C>
+where
+    T: uDebug,
+    S: Storage<T>,
+    C: Cursor,
+{
+    /// Prints the occupancy and the elements from oldest to newest, leaving out the slot
+    /// layout
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        f.debug_struct(S::NAME)?
+            .field("len", &self.len())?
+            .field("capacity", &self.capacity())?
+            .field("contents", &Contents(self))?
+            .finish()
+    }
+}
+
+impl<T, S, P, C> uDisplay for GenericRingBuffer<T, S, P, C>
+where
+    T: uDisplay,
+    S: Storage<T>,
+    C: Cursor,
+{
+    /// Prints the elements from oldest to newest as a bracketed list
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        f.write_char('[')?;
+        for (index, data) in self.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            uDisplay::fmt(data, f)?;
+        }
+        f.write_char(']')
+    }
+}
+
+/// Prints the elements of a ring buffer as a debug list
+struct Contents<'a, T, S: Storage<T>, P, C: Cursor>(&'a GenericRingBuffer<T, S, P, C>);
+
+impl<T, S, P, C> uDebug for Contents<'_, T, S, P, C>
+where
+    T: uDebug,
+    S: Storage<T>,
+    C: Cursor,
+{
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        f.debug_list()?.entries(self.0.iter())?.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RingBuffer;
+
+    #[test]
+    fn udebug_logical_contents() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+        ring_buffer.extend([1, 2, 3, 4]);
+
+        let mut output = String::new();
+        ufmt::uwrite!(output, "{:?}", ring_buffer).unwrap();
+        assert_eq!(
+            output,
+            "RingBuffer { len: 3, capacity: 3, contents: [2, 3, 4] }"
+        );
+    }
+
+    #[test]
+    fn udisplay_list() {
+        let mut ring_buffer: RingBuffer<i32, 4> = RingBuffer::new();
+
+        let mut output = String::new();
+        ufmt::uwrite!(output, "{}", ring_buffer).unwrap();
+        ring_buffer.extend([-1, 20]);
+        ufmt::uwrite!(output, " {}", ring_buffer).unwrap();
+        assert_eq!(output, "[] [-1, 20]");
+    }
+}
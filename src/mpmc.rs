@@ -0,0 +1,332 @@
+//! A lock-free bounded multi-producer multi-consumer ring buffer
+//!
+//! [`Queue`] is shared by reference, so any number of threads can write and read through the
+//! same instance. Each slot carries a stamp recording which lap of the buffer it was last
+//! written or read in, following Dmitry Vyukov's bounded queue, so threads only contend on the
+//! cursor they advance
+//!
+//! # Examples
+//!
+//! ```
+//! let queue: sringbuf::mpmc::Queue<u32, 8> = sringbuf::mpmc::Queue::new();
+//!
+//! std::thread::scope(|scope| {
+//!     for _ in 0..2 {
+//!         scope.spawn(|| {
+//!             for i in 0..4 {
+//!                 while queue.try_write(i).is_err() {}
+//!             }
+//!         });
+//!     }
+//! });
+//!
+//! let mut sum = 0;
+//! while let Some(data) = queue.read() {
+//!     sum += data;
+//! }
+//! assert_eq!(sum, 12);
+//! ```
+use core::fmt;
+use core::hint;
+use core::sync::atomic::{self, AtomicUsize, Ordering};
+
+use crate::Full;
+use crate::lap::{self, Lap, Slot};
+use crate::padding::CachePadded;
+
+/// A fixed-capacity FIFO queue holding up to `N` elements inline that can be written and read
+/// from many threads at once
+///
+/// Cursors hold a slot index in their low bits and a lap count above them, so they wrap
+/// correctly for any `N`, not just powers of two
+pub struct Queue<T, const N: usize> {
+    contents: [Slot<T>; N],
+    head: CachePadded<AtomicUsize>,
+    tail: CachePadded<AtomicUsize>,
+}
+
+// SAFETY: an element is written by exactly one thread, which won the slot through the tail
+// cursor, and read by exactly one thread, which won it through the head cursor; the stamps
+// order those accesses, so sharing a queue only moves `T` values across threads
+unsafe impl<T: Send, const N: usize> Send for Queue<T, N> {}
+
+// SAFETY: see the `Send` impl
+unsafe impl<T: Send, const N: usize> Sync for Queue<T, N> {}
+
+impl<T, const N: usize> Queue<T, N> {
+    /// Creates a new Queue instance
+    ///
+    /// A capacity of zero is rejected at compile time
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let queue: sringbuf::mpmc::Queue<u8, 5> = sringbuf::mpmc::Queue::new();
+    /// assert!(queue.is_empty());
+    /// ```
+    ///
+    /// ```compile_fail
+    /// let queue: sringbuf::mpmc::Queue<u8, 0> = sringbuf::mpmc::Queue::new();
+    /// ```
+    pub const fn new() -> Queue<T, N> {
+        const { assert!(N > 0, "queue capacity must not be zero") }
+
+        Queue {
+            contents: lap::slots(),
+            head: CachePadded::new(AtomicUsize::new(0)),
+            tail: CachePadded::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Writes a value to a queue if it has free space, handing the value back in [`Full`]
+    /// otherwise
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let queue: sringbuf::mpmc::Queue<u8, 1> = sringbuf::mpmc::Queue::new();
+    /// assert_eq!(queue.try_write(1), Ok(()));
+    /// assert_eq!(queue.try_write(2), Err(sringbuf::Full(2)));
+    /// ```
+    pub fn try_write(&self, data: T) -> Result<(), Full<T>> {
+        let mut tail = self.tail.load(Ordering::Relaxed);
+
+        loop {
+            let slot = &self.contents[Lap::<N>::index(tail)];
+            let stamp = slot.stamp.load(Ordering::Acquire);
+
+            if stamp == tail {
+                match self.tail.compare_exchange_weak(
+                    tail,
+                    Lap::<N>::next(tail),
+                    Ordering::SeqCst,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        // SAFETY: winning the exchange gives this thread the free slot until
+                        // its stamp is published
+                        unsafe { (*slot.data.get()).write(data) };
+                        slot.stamp.store(tail + 1, Ordering::Release);
+                        return Ok(());
+                    }
+                    Err(current) => tail = current,
+                }
+            } else if stamp.wrapping_add(Lap::<N>::ONE) == tail + 1 {
+                // The slot still holds the element from the previous lap
+                atomic::fence(Ordering::SeqCst);
+                let head = self.head.load(Ordering::Relaxed);
+                if head.wrapping_add(Lap::<N>::ONE) == tail {
+                    return Err(Full(data));
+                }
+
+                tail = self.tail.load(Ordering::Relaxed);
+            } else {
+                // Another writer claimed the slot and has not published it yet
+                hint::spin_loop();
+                tail = self.tail.load(Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Reads the oldest element from a queue, or returns `None` if it is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let queue: sringbuf::mpmc::Queue<u8, 2> = sringbuf::mpmc::Queue::new();
+    /// queue.try_write(1).unwrap();
+    /// assert_eq!(queue.read(), Some(1));
+    /// assert_eq!(queue.read(), None);
+    /// ```
+    pub fn read(&self) -> Option<T> {
+        let mut head = self.head.load(Ordering::Relaxed);
+
+        loop {
+            let slot = &self.contents[Lap::<N>::index(head)];
+            let stamp = slot.stamp.load(Ordering::Acquire);
+
+            if stamp == head + 1 {
+                match self.head.compare_exchange_weak(
+                    head,
+                    Lap::<N>::next(head),
+                    Ordering::SeqCst,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        // SAFETY: the stamp shows the slot was published, and winning the
+                        // exchange gives this thread sole access to it until the next stamp
+                        let data = unsafe { (*slot.data.get()).assume_init_read() };
+                        slot.stamp
+                            .store(head.wrapping_add(Lap::<N>::ONE), Ordering::Release);
+                        return Some(data);
+                    }
+                    Err(current) => head = current,
+                }
+            } else if stamp == head {
+                // The slot has not been written in this lap
+                atomic::fence(Ordering::SeqCst);
+                let tail = self.tail.load(Ordering::Relaxed);
+                if tail == head {
+                    return None;
+                }
+
+                head = self.head.load(Ordering::Relaxed);
+            } else {
+                // Another reader claimed the slot and has not released it yet
+                hint::spin_loop();
+                head = self.head.load(Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Returns the number of elements in a queue
+    ///
+    /// Other threads may write or read concurrently, so the result is only a snapshot
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let queue: sringbuf::mpmc::Queue<u8, 5> = sringbuf::mpmc::Queue::new();
+    /// queue.try_write(1).unwrap();
+    /// assert_eq!(queue.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        loop {
+            let tail = self.tail.load(Ordering::SeqCst);
+            let head = self.head.load(Ordering::SeqCst);
+
+            // Retry until both cursors were read without the tail moving in between
+            if self.tail.load(Ordering::SeqCst) == tail {
+                return Lap::<N>::distance(head, tail);
+            }
+        }
+    }
+
+    /// Returns `true` if a queue holds no elements
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if a queue holds `N` elements
+    pub fn is_full(&self) -> bool {
+        self.len() == N
+    }
+
+    /// Returns the maximum number of elements a queue can hold
+    pub fn capacity(&self) -> usize {
+        N
+    }
+}
+
+impl<T, const N: usize> Drop for Queue<T, N> {
+    /// Drops the elements that were never read
+    fn drop(&mut self) {
+        while self.read().is_some() {}
+    }
+}
+
+impl<T, const N: usize> Default for Queue<T, N> {
+    /// Creates an empty queue
+    fn default() -> Queue<T, N> {
+        Queue::new()
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for Queue<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Queue")
+            .field("len", &self.len())
+            .field("capacity", &N)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Full;
+    use crate::mpmc::Queue;
+
+    use std::rc::Rc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+
+    #[test]
+    fn try_write_full() {
+        let queue: Queue<u8, 2> = Queue::new();
+
+        assert_eq!(queue.try_write(1), Ok(()));
+        assert_eq!(queue.try_write(2), Ok(()));
+        assert!(queue.is_full());
+        assert_eq!(queue.try_write(3), Err(Full(3)));
+        assert_eq!(queue.read(), Some(1));
+        assert_eq!(queue.try_write(3), Ok(()));
+    }
+
+    #[test]
+    fn read_wrap_around() {
+        let queue: Queue<u32, 3> = Queue::new();
+
+        for i in 0..20 {
+            queue.try_write(i).unwrap();
+            queue.try_write(i + 100).unwrap();
+            assert_eq!(queue.len(), 2);
+            assert_eq!(queue.read(), Some(i));
+            assert_eq!(queue.read(), Some(i + 100));
+            assert_eq!(queue.read(), None);
+        }
+    }
+
+    #[test]
+    fn drop_unread_elements() {
+        let counter = Rc::new(());
+        let queue: Queue<Rc<()>, 3> = Queue::new();
+
+        for _ in 0..3 {
+            queue.try_write(Rc::clone(&counter)).unwrap();
+        }
+        drop(queue.read());
+        assert_eq!(Rc::strong_count(&counter), 3);
+
+        drop(queue);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn threads_deliver_every_element_once() {
+        const THREADS: usize = 4;
+        const COUNT: usize = if cfg!(miri) { 50 } else { 20_000 };
+
+        let queue: Queue<usize, 5> = Queue::new();
+        let received = AtomicUsize::new(0);
+        let sum = AtomicUsize::new(0);
+
+        thread::scope(|scope| {
+            for _ in 0..THREADS {
+                scope.spawn(|| {
+                    for i in 0..COUNT {
+                        let mut data = i;
+                        while let Err(Full(rejected)) = queue.try_write(data) {
+                            data = rejected;
+                            thread::yield_now();
+                        }
+                    }
+                });
+
+                scope.spawn(|| {
+                    while received.load(Ordering::Relaxed) < THREADS * COUNT {
+                        match queue.read() {
+                            Some(data) => {
+                                sum.fetch_add(data, Ordering::Relaxed);
+                                received.fetch_add(1, Ordering::Relaxed);
+                            }
+                            None => thread::yield_now(),
+                        }
+                    }
+                });
+            }
+        });
+
+        assert!(queue.is_empty());
+        assert_eq!(sum.into_inner(), THREADS * COUNT * (COUNT - 1) / 2);
+    }
+}
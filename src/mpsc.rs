@@ -0,0 +1,417 @@
+//! A lock-free bounded multi-producer single-consumer ring buffer
+//!
+//! [`Queue::split`] hands out a [`Producer`] that can be cloned for every writing thread and a
+//! single [`Consumer`]. Producers claim slots like in [`mpmc::Queue`](crate::mpmc::Queue), but
+//! the consumer owns its cursor outright, so reading never loops or compare-exchanges
+//!
+//! # Examples
+//!
+//! ```
+//! let mut queue: sringbuf::mpsc::Queue<u32, 8> = sringbuf::mpsc::Queue::new();
+//! let (producer, mut consumer) = queue.split();
+//!
+//! std::thread::scope(|scope| {
+//!     for _ in 0..2 {
+//!         let producer = producer.clone();
+//!         scope.spawn(move || {
+//!             for i in 0..4 {
+//!                 while producer.try_write(i).is_err() {}
+//!             }
+//!         });
+//!     }
+//! });
+//!
+//! let mut sum = 0;
+//! while let Some(data) = consumer.read() {
+//!     sum += data;
+//! }
+//! assert_eq!(sum, 12);
+//! ```
+use core::fmt;
+use core::hint;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::Full;
+use crate::lap::{self, Lap, Slot};
+use crate::padding::CachePadded;
+
+/// A fixed-capacity FIFO queue holding up to `N` elements inline that many threads can write
+/// to and one thread reads from
+///
+/// A queue is used through the halves returned by [`Queue::split`]
+pub struct Queue<T, const N: usize> {
+    contents: [Slot<T>; N],
+    head: CachePadded<AtomicUsize>,
+    tail: CachePadded<AtomicUsize>,
+}
+
+// SAFETY: an element is written by exactly one producer, which won the slot through the tail
+// cursor, and read by the single consumer; the stamps order those accesses, so sharing a queue
+// only moves `T` values across threads
+unsafe impl<T: Send, const N: usize> Send for Queue<T, N> {}
+
+// SAFETY: see the `Send` impl
+unsafe impl<T: Send, const N: usize> Sync for Queue<T, N> {}
+
+/// A writing half of a split queue, which can be cloned to write from several threads
+pub struct Producer<'a, T, const N: usize> {
+    queue: &'a Queue<T, N>,
+}
+
+/// The reading half of a split queue
+pub struct Consumer<'a, T, const N: usize> {
+    queue: &'a Queue<T, N>,
+}
+
+impl<T, const N: usize> Queue<T, N> {
+    /// Creates a new Queue instance
+    ///
+    /// A capacity of zero is rejected at compile time
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let queue: sringbuf::mpsc::Queue<u8, 5> = sringbuf::mpsc::Queue::new();
+    /// assert!(queue.is_empty());
+    /// ```
+    ///
+    /// ```compile_fail
+    /// let queue: sringbuf::mpsc::Queue<u8, 0> = sringbuf::mpsc::Queue::new();
+    /// ```
+    pub const fn new() -> Queue<T, N> {
+        const { assert!(N > 0, "queue capacity must not be zero") }
+
+        Queue {
+            contents: lap::slots(),
+            head: CachePadded::new(AtomicUsize::new(0)),
+            tail: CachePadded::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Splits a queue into a [`Producer`] and the [`Consumer`], both borrowing it
+    ///
+    /// Borrowing the queue mutably guarantees there is only ever one consumer
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut queue: sringbuf::mpsc::Queue<u8, 2> = sringbuf::mpsc::Queue::new();
+    /// let (producer, mut consumer) = queue.split();
+    /// producer.try_write(1).unwrap();
+    /// producer.clone().try_write(2).unwrap();
+    /// assert_eq!(consumer.read(), Some(1));
+    /// assert_eq!(consumer.read(), Some(2));
+    /// ```
+    pub fn split(&mut self) -> (Producer<'_, T, N>, Consumer<'_, T, N>) {
+        (Producer { queue: self }, Consumer { queue: self })
+    }
+
+    /// Returns the number of elements in a queue
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut queue: sringbuf::mpsc::Queue<u8, 5> = sringbuf::mpsc::Queue::new();
+    /// queue.split().0.try_write(1).unwrap();
+    /// assert_eq!(queue.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        loop {
+            let tail = self.tail.load(Ordering::SeqCst);
+            let head = self.head.load(Ordering::SeqCst);
+
+            // Retry until both cursors were read without the tail moving in between
+            if self.tail.load(Ordering::SeqCst) == tail {
+                return Lap::<N>::distance(head, tail);
+            }
+        }
+    }
+
+    /// Returns `true` if a queue holds no elements
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if a queue holds `N` elements
+    pub fn is_full(&self) -> bool {
+        self.len() == N
+    }
+
+    /// Returns the maximum number of elements a queue can hold
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Writes a value on behalf of any producer
+    fn try_write(&self, data: T) -> Result<(), Full<T>> {
+        let mut tail = self.tail.load(Ordering::Relaxed);
+
+        loop {
+            let slot = &self.contents[Lap::<N>::index(tail)];
+            let stamp = slot.stamp.load(Ordering::Acquire);
+
+            if stamp == tail {
+                match self.tail.compare_exchange_weak(
+                    tail,
+                    Lap::<N>::next(tail),
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        // SAFETY: winning the exchange gives this producer the free slot until
+                        // its stamp is published
+                        unsafe { (*slot.data.get()).write(data) };
+                        slot.stamp.store(tail + 1, Ordering::Release);
+                        return Ok(());
+                    }
+                    Err(current) => tail = current,
+                }
+            } else if stamp.wrapping_add(Lap::<N>::ONE) == tail + 1 {
+                // The consumer has not yet read the element from the previous lap, so the
+                // queue is full unless `tail` was stale
+                let current = self.tail.load(Ordering::Relaxed);
+                if current == tail {
+                    return Err(Full(data));
+                }
+
+                tail = current;
+            } else {
+                // Another producer claimed the slot and has not published it yet
+                hint::spin_loop();
+                tail = self.tail.load(Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Reads a value on behalf of the consumer, which has exclusive use of `head`
+    fn read(&self) -> Option<T> {
+        let head = self.head.load(Ordering::Relaxed);
+        let slot = &self.contents[Lap::<N>::index(head)];
+        if slot.stamp.load(Ordering::Acquire) != head + 1 {
+            // Either the queue is empty or the producer that claimed the slot is mid-write
+            return None;
+        }
+
+        // SAFETY: the stamp shows the slot was published, and producers do not touch it until
+        // its stamp is moved to the next lap
+        let data = unsafe { (*slot.data.get()).assume_init_read() };
+        slot.stamp
+            .store(head.wrapping_add(Lap::<N>::ONE), Ordering::Release);
+        self.head.store(Lap::<N>::next(head), Ordering::Release);
+        Some(data)
+    }
+}
+
+impl<T, const N: usize> Producer<'_, T, N> {
+    /// Writes a value to the queue if it has free space, handing the value back in [`Full`]
+    /// otherwise
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut queue: sringbuf::mpsc::Queue<u8, 1> = sringbuf::mpsc::Queue::new();
+    /// let (producer, _consumer) = queue.split();
+    /// assert_eq!(producer.try_write(1), Ok(()));
+    /// assert_eq!(producer.try_write(2), Err(sringbuf::Full(2)));
+    /// ```
+    pub fn try_write(&self, data: T) -> Result<(), Full<T>> {
+        self.queue.try_write(data)
+    }
+
+    /// Returns the number of elements in the queue
+    ///
+    /// Other threads may write or read concurrently, so the result is only a snapshot
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns `true` if the queue holds no elements
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Returns `true` if the queue holds `N` elements
+    pub fn is_full(&self) -> bool {
+        self.queue.is_full()
+    }
+
+    /// Returns the maximum number of elements the queue can hold
+    pub fn capacity(&self) -> usize {
+        N
+    }
+}
+
+impl<T, const N: usize> Consumer<'_, T, N> {
+    /// Reads the oldest element from the queue, or returns `None` if it is empty
+    ///
+    /// An element whose producer is still writing it is not visible yet, so `None` may be
+    /// returned while a write is in progress
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut queue: sringbuf::mpsc::Queue<u8, 1> = sringbuf::mpsc::Queue::new();
+    /// let (producer, mut consumer) = queue.split();
+    /// producer.try_write(1).unwrap();
+    /// assert_eq!(consumer.read(), Some(1));
+    /// assert_eq!(consumer.read(), None);
+    /// ```
+    pub fn read(&mut self) -> Option<T> {
+        self.queue.read()
+    }
+
+    /// Returns the number of elements in the queue
+    ///
+    /// Producers may write concurrently, so the result is only a snapshot
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns `true` if the queue holds no elements
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Returns `true` if the queue holds `N` elements
+    pub fn is_full(&self) -> bool {
+        self.queue.is_full()
+    }
+
+    /// Returns the maximum number of elements the queue can hold
+    pub fn capacity(&self) -> usize {
+        N
+    }
+}
+
+impl<'a, T, const N: usize> Clone for Producer<'a, T, N> {
+    fn clone(&self) -> Producer<'a, T, N> {
+        Producer { queue: self.queue }
+    }
+}
+
+impl<T, const N: usize> Drop for Queue<T, N> {
+    /// Drops the elements that were never read
+    fn drop(&mut self) {
+        while self.read().is_some() {}
+    }
+}
+
+impl<T, const N: usize> Default for Queue<T, N> {
+    /// Creates an empty queue
+    fn default() -> Queue<T, N> {
+        Queue::new()
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for Queue<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Queue")
+            .field("len", &self.len())
+            .field("capacity", &N)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for Producer<'_, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Producer").field(self.queue).finish()
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for Consumer<'_, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Consumer").field(self.queue).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Full;
+    use crate::mpsc::Queue;
+
+    use std::rc::Rc;
+    use std::thread;
+
+    #[test]
+    fn try_write_full() {
+        let mut queue: Queue<u8, 2> = Queue::new();
+        let (producer, mut consumer) = queue.split();
+
+        assert_eq!(producer.try_write(1), Ok(()));
+        assert_eq!(producer.try_write(2), Ok(()));
+        assert!(producer.is_full());
+        assert_eq!(producer.try_write(3), Err(Full(3)));
+        assert_eq!(consumer.read(), Some(1));
+        assert_eq!(producer.try_write(3), Ok(()));
+    }
+
+    #[test]
+    fn read_wrap_around() {
+        let mut queue: Queue<u32, 3> = Queue::new();
+        let (producer, mut consumer) = queue.split();
+
+        for i in 0..20 {
+            producer.try_write(i).unwrap();
+            producer.try_write(i + 100).unwrap();
+            assert_eq!(consumer.len(), 2);
+            assert_eq!(consumer.read(), Some(i));
+            assert_eq!(consumer.read(), Some(i + 100));
+            assert_eq!(consumer.read(), None);
+        }
+    }
+
+    #[test]
+    fn drop_unread_elements() {
+        let counter = Rc::new(());
+        let mut queue: Queue<Rc<()>, 3> = Queue::new();
+        let (producer, mut consumer) = queue.split();
+
+        for _ in 0..3 {
+            producer.try_write(Rc::clone(&counter)).unwrap();
+        }
+        drop(consumer.read());
+        assert_eq!(Rc::strong_count(&counter), 3);
+
+        drop(queue);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn threads_keep_per_producer_order() {
+        const THREADS: usize = 4;
+        const COUNT: usize = if cfg!(miri) { 50 } else { 20_000 };
+
+        let mut queue: Queue<(usize, usize), 5> = Queue::new();
+        let (producer, mut consumer) = queue.split();
+
+        thread::scope(|scope| {
+            for thread in 0..THREADS {
+                let producer = producer.clone();
+                scope.spawn(move || {
+                    for i in 0..COUNT {
+                        let mut data = (thread, i);
+                        while let Err(Full(rejected)) = producer.try_write(data) {
+                            data = rejected;
+                            thread::yield_now();
+                        }
+                    }
+                });
+            }
+
+            let mut next = [0; THREADS];
+            let mut received = 0;
+            while received < THREADS * COUNT {
+                match consumer.read() {
+                    Some((thread, i)) => {
+                        assert_eq!(i, next[thread]);
+                        next[thread] += 1;
+                        received += 1;
+                    }
+                    None => thread::yield_now(),
+                }
+            }
+        });
+
+        assert!(consumer.is_empty());
+    }
+}
@@ -0,0 +1,51 @@
+//! Cache-line alignment for the cursors of the lock-free queues
+use core::ops::{Deref, DerefMut};
+
+/// Aligns and pads a value to a cache line, so writes to it never invalidate the line holding
+/// a neighbouring value
+///
+/// Lines are assumed to be 64 bytes, or 128 bytes with the `cache-line-128` feature, which suits
+/// targets such as Apple silicon that prefetch lines in pairs
+#[cfg_attr(not(feature = "cache-line-128"), repr(align(64)))]
+#[cfg_attr(feature = "cache-line-128", repr(align(128)))]
+pub(crate) struct CachePadded<T>(T);
+
+impl<T> CachePadded<T> {
+    pub(crate) const fn new(value: T) -> CachePadded<T> {
+        CachePadded(value)
+    }
+}
+
+impl<T> Deref for CachePadded<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for CachePadded<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::padding::CachePadded;
+
+    use core::mem;
+    use core::sync::atomic::AtomicUsize;
+
+    #[test]
+    fn fills_a_cache_line() {
+        let line = if cfg!(feature = "cache-line-128") {
+            128
+        } else {
+            64
+        };
+
+        assert_eq!(mem::align_of::<CachePadded<AtomicUsize>>(), line);
+        assert_eq!(mem::size_of::<[CachePadded<AtomicUsize>; 2]>(), 2 * line);
+    }
+}
@@ -0,0 +1,68 @@
+//! Policies deciding what [`RingBuffer::write`](crate::RingBuffer::write) does when a ring
+//! buffer is full
+//!
+//! The policy is chosen through the third type parameter of
+//! [`RingBuffer`](crate::RingBuffer), which defaults to [`Overwrite`]. It is resolved at compile
+//! time, so selecting a policy has no runtime cost
+//!
+//! # Examples
+//!
+//! ```
+//! use sringbuf::policy::Reject;
+//!
+//! let mut ring_buffer: sringbuf::RingBuffer<u8, 1, Reject> = sringbuf::RingBuffer::new();
+//! ring_buffer.write(1);
+//! ring_buffer.write(2);
+//! assert_eq!(ring_buffer.read(), Some(1));
+//! ```
+
+/// Overwrites the oldest unread element with the new one
+///
+/// The read cursor is pushed past the overwritten element, so reads keep returning elements
+/// strictly from oldest to newest
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Overwrite;
+
+/// Discards the new element, keeping the unread elements intact
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Reject;
+
+/// Panics when writing to a full ring buffer
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Panic;
+
+/// The behavior of a ring buffer on overflow
+///
+/// This trait is sealed and implemented by [`Overwrite`], [`Reject`] and [`Panic`]
+pub trait OverflowPolicy: sealed::Sealed {}
+
+impl OverflowPolicy for Overwrite {}
+
+impl OverflowPolicy for Reject {}
+
+impl OverflowPolicy for Panic {}
+
+pub(crate) mod sealed {
+    /// What a write does when the ring buffer is full
+    pub enum Overflow {
+        Overwrite,
+        Reject,
+        Panic,
+    }
+
+    pub trait Sealed {
+        const OVERFLOW: Overflow;
+    }
+
+    impl Sealed for super::Overwrite {
+        const OVERFLOW: Overflow = Overflow::Overwrite;
+    }
+
+    impl Sealed for super::Reject {
+        const OVERFLOW: Overflow = Overflow::Reject;
+    }
+
+    impl Sealed for super::Panic {
+        const OVERFLOW: Overflow = Overflow::Panic;
+    }
+}
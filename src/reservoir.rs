@@ -0,0 +1,145 @@
+//! A buffer keeping a uniform random sample of everything written to it
+use core::mem;
+use core::ops::Deref;
+
+use crate::RingBuffer;
+
+/// A buffer of up to `N` elements that are a uniform random sample of every value ever
+/// written, kept with the classic reservoir algorithm
+///
+/// The first `N` writes are stored as they come; after that, each write replaces a random
+/// element with a probability that shrinks as more values are seen. The order of the retained
+/// elements carries no meaning. Randomness comes from a small generator seeded at construction,
+/// so a given seed always yields the same sample
+#[derive(Clone, Debug)]
+pub struct ReservoirBuffer<T, const N: usize> {
+    ring_buffer: RingBuffer<T, N>,
+    seen: u64,
+    state: u64,
+}
+
+impl<T, const N: usize> ReservoirBuffer<T, N> {
+    /// Creates a new, empty ReservoirBuffer instance whose random choices derive from `seed`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let reservoir: sringbuf::ReservoirBuffer<u8, 5> = sringbuf::ReservoirBuffer::new(42);
+    /// assert_eq!(reservoir.seen(), 0);
+    /// ```
+    pub const fn new(seed: u64) -> ReservoirBuffer<T, N> {
+        ReservoirBuffer {
+            ring_buffer: RingBuffer::new(),
+            seen: 0,
+            state: seed,
+        }
+    }
+
+    /// Offers data to the sample, returning the element that is not retained: `data` itself or
+    /// the element it replaced
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut reservoir: sringbuf::ReservoirBuffer<u32, 10> = sringbuf::ReservoirBuffer::new(7);
+    /// reservoir.extend(0..1000);
+    /// assert_eq!(reservoir.len(), 10);
+    /// assert_eq!(reservoir.seen(), 1000);
+    /// assert!(reservoir.iter().all(|data| *data < 1000));
+    /// ```
+    pub fn write(&mut self, data: T) -> Option<T> {
+        self.seen += 1;
+        if !self.ring_buffer.is_full() {
+            return self.ring_buffer.write(data);
+        }
+
+        let index = self.next_below(self.seen);
+        match self.ring_buffer.get_mut(index as usize) {
+            Some(slot) => Some(mem::replace(slot, data)),
+            None => Some(data),
+        }
+    }
+
+    /// Returns the number of values written since the buffer was created or cleared
+    pub fn seen(&self) -> u64 {
+        self.seen
+    }
+
+    /// Drops the sample and starts a new one, keeping the state of the random generator
+    pub fn clear(&mut self) {
+        self.ring_buffer.clear();
+        self.seen = 0;
+    }
+
+    /// Returns the underlying ring buffer holding the sample
+    pub fn into_inner(self) -> RingBuffer<T, N> {
+        self.ring_buffer
+    }
+
+    /// Returns a pseudo-random value below `bound`, using splitmix64 and a multiply-shift
+    /// reduction
+    fn next_below(&mut self, bound: u64) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+
+        ((u128::from(z) * u128::from(bound)) >> 64) as u64
+    }
+}
+
+impl<T, const N: usize> Deref for ReservoirBuffer<T, N> {
+    type Target = RingBuffer<T, N>;
+
+    fn deref(&self) -> &RingBuffer<T, N> {
+        &self.ring_buffer
+    }
+}
+
+impl<T, const N: usize> Extend<T> for ReservoirBuffer<T, N> {
+    /// Offers every element of an iterator to the sample
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for data in iter {
+            self.write(data);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ReservoirBuffer;
+
+    #[test]
+    fn write_fills_before_sampling() {
+        let mut reservoir: ReservoirBuffer<u32, 3> = ReservoirBuffer::new(0);
+
+        assert_eq!(reservoir.write(0), None);
+        reservoir.extend(1..3);
+        assert!(reservoir.iter().eq(&[0, 1, 2]));
+        assert!(reservoir.write(3).is_some());
+        assert_eq!(reservoir.len(), 3);
+
+        reservoir.clear();
+        assert_eq!(reservoir.seen(), 0);
+        assert_eq!(reservoir.write(4), None);
+    }
+
+    #[test]
+    fn write_samples_uniformly() {
+        let mut counts = [0u32; 8];
+        for seed in 0..2000 {
+            let mut reservoir: ReservoirBuffer<usize, 4> = ReservoirBuffer::new(seed);
+            reservoir.extend(0..8);
+            for &data in reservoir.iter() {
+                counts[data] += 1;
+            }
+        }
+
+        // Every value is retained with probability 4 / 8
+        assert!(
+            counts.iter().all(|&count| (850..1150).contains(&count)),
+            "{counts:?}"
+        );
+    }
+}
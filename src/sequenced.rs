@@ -0,0 +1,202 @@
+//! A ring buffer that numbers its elements to report overwritten ones
+use core::ops::Deref;
+
+use crate::policy::sealed::Overflow;
+use crate::policy::{OverflowPolicy, Overwrite};
+use crate::{Lagged, RingBuffer};
+
+/// A FIFO ring buffer that stamps every write with a monotonic sequence number
+///
+/// Reads return each element with its sequence number, and report through [`Lagged`] how many
+/// elements were overwritten since the previous read
+#[derive(Clone, Debug)]
+pub struct SequencedRingBuffer<T, const N: usize, P = Overwrite> {
+    ring_buffer: RingBuffer<T, N, P>,
+    next: u64,
+    cursor: u64,
+}
+
+impl<T, const N: usize, P> SequencedRingBuffer<T, N, P> {
+    /// Creates a new SequencedRingBuffer instance whose first write gets sequence number 0
+    ///
+    /// A capacity of zero is rejected at compile time
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let ring_buffer: sringbuf::SequencedRingBuffer<u8, 5> = sringbuf::SequencedRingBuffer::new();
+    /// assert_eq!(ring_buffer.next_sequence(), 0);
+    /// ```
+    pub const fn new() -> SequencedRingBuffer<T, N, P> {
+        SequencedRingBuffer {
+            ring_buffer: RingBuffer::new(),
+            next: 0,
+            cursor: 0,
+        }
+    }
+
+    /// Writes data to a ring buffer, giving it the next sequence number
+    ///
+    /// The overflow policy `P` applies as for
+    /// [`GenericRingBuffer::write`](crate::GenericRingBuffer::write); a rejected value does not
+    /// use up a sequence number
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::SequencedRingBuffer<u8, 1> = sringbuf::SequencedRingBuffer::new();
+    /// assert_eq!(ring_buffer.write(1), None);
+    /// assert_eq!(ring_buffer.write(2), Some(1));
+    /// assert_eq!(ring_buffer.next_sequence(), 2);
+    /// ```
+    pub fn write(&mut self, data: T) -> Option<T>
+    where
+        P: OverflowPolicy,
+    {
+        let accepted = !self.ring_buffer.is_full() || matches!(P::OVERFLOW, Overflow::Overwrite);
+        let displaced = self.ring_buffer.write(data);
+        if accepted {
+            self.next += 1;
+        }
+        displaced
+    }
+
+    /// Reads the oldest element from a ring buffer along with its sequence number, or returns
+    /// `None` if it is empty
+    ///
+    /// If elements were overwritten since the previous read, the first read after that
+    /// returns [`Lagged`] with how many, and the following reads resume at the oldest element
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::SequencedRingBuffer<u8, 2> = sringbuf::SequencedRingBuffer::new();
+    /// ring_buffer.extend([1, 2, 3, 4, 5]);
+    /// assert_eq!(ring_buffer.read(), Err(sringbuf::Lagged(3)));
+    /// assert_eq!(ring_buffer.read(), Ok(Some((3, 4))));
+    /// assert_eq!(ring_buffer.read(), Ok(Some((4, 5))));
+    /// assert_eq!(ring_buffer.read(), Ok(None));
+    /// ```
+    pub fn read(&mut self) -> Result<Option<(u64, T)>, Lagged> {
+        let oldest = self.oldest_sequence();
+        if self.cursor < oldest {
+            let skipped = oldest - self.cursor;
+            self.cursor = oldest;
+            return Err(Lagged(skipped));
+        }
+
+        Ok(self.ring_buffer.read().map(|data| {
+            self.cursor += 1;
+            (oldest, data)
+        }))
+    }
+
+    /// Returns the sequence number of the oldest element in a ring buffer, or the next sequence
+    /// number if it is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::SequencedRingBuffer<u8, 2> = sringbuf::SequencedRingBuffer::new();
+    /// ring_buffer.extend([1, 2, 3]);
+    /// assert_eq!(ring_buffer.oldest_sequence(), 1);
+    /// ```
+    pub fn oldest_sequence(&self) -> u64 {
+        self.next - self.ring_buffer.len() as u64
+    }
+
+    /// Returns the sequence number the next write will get
+    pub fn next_sequence(&self) -> u64 {
+        self.next
+    }
+
+    /// Drops every element of a ring buffer without counting them as lagged
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::SequencedRingBuffer<u8, 2> = sringbuf::SequencedRingBuffer::new();
+    /// ring_buffer.extend([1, 2, 3]);
+    /// ring_buffer.clear();
+    /// ring_buffer.write(4);
+    /// assert_eq!(ring_buffer.read(), Ok(Some((3, 4))));
+    /// ```
+    pub fn clear(&mut self) {
+        self.ring_buffer.clear();
+        self.cursor = self.next;
+    }
+
+    /// Returns the underlying ring buffer, dropping the sequence numbers
+    pub fn into_inner(self) -> RingBuffer<T, N, P> {
+        self.ring_buffer
+    }
+}
+
+impl<T, const N: usize, P> Deref for SequencedRingBuffer<T, N, P> {
+    type Target = RingBuffer<T, N, P>;
+
+    fn deref(&self) -> &RingBuffer<T, N, P> {
+        &self.ring_buffer
+    }
+}
+
+impl<T, const N: usize, P> Default for SequencedRingBuffer<T, N, P> {
+    /// Creates an empty ring buffer
+    fn default() -> SequencedRingBuffer<T, N, P> {
+        SequencedRingBuffer::new()
+    }
+}
+
+impl<T, const N: usize, P: OverflowPolicy> Extend<T> for SequencedRingBuffer<T, N, P> {
+    /// Writes every element of an iterator to a ring buffer
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for data in iter {
+            self.write(data);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::policy::Reject;
+    use crate::{Lagged, SequencedRingBuffer};
+
+    #[test]
+    fn read_sequence_numbers() {
+        let mut ring_buffer: SequencedRingBuffer<char, 3> = SequencedRingBuffer::new();
+
+        ring_buffer.write('a');
+        ring_buffer.write('b');
+        assert_eq!(ring_buffer.read(), Ok(Some((0, 'a'))));
+        ring_buffer.write('c');
+        assert_eq!(ring_buffer.read(), Ok(Some((1, 'b'))));
+        assert_eq!(ring_buffer.read(), Ok(Some((2, 'c'))));
+        assert_eq!(ring_buffer.read(), Ok(None));
+        assert_eq!(ring_buffer.next_sequence(), 3);
+    }
+
+    #[test]
+    fn read_lagged_once() {
+        let mut ring_buffer: SequencedRingBuffer<u32, 4> = SequencedRingBuffer::new();
+
+        ring_buffer.extend(0..6);
+        assert_eq!(ring_buffer.read(), Err(Lagged(2)));
+        assert_eq!(ring_buffer.read(), Ok(Some((2, 2))));
+        ring_buffer.extend(6..12);
+        assert_eq!(ring_buffer.read(), Err(Lagged(5)));
+        assert_eq!(ring_buffer.read(), Ok(Some((8, 8))));
+        assert_eq!(ring_buffer.len(), 3);
+    }
+
+    #[test]
+    fn write_reject_keeps_sequence() {
+        let mut ring_buffer: SequencedRingBuffer<u8, 2, Reject> = SequencedRingBuffer::new();
+
+        ring_buffer.extend([1, 2, 3]);
+        assert_eq!(ring_buffer.next_sequence(), 2);
+        assert_eq!(ring_buffer.read(), Ok(Some((0, 1))));
+        ring_buffer.write(4);
+        assert_eq!(ring_buffer.read(), Ok(Some((1, 2))));
+        assert_eq!(ring_buffer.read(), Ok(Some((2, 4))));
+    }
+}
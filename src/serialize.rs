@@ -0,0 +1,98 @@
+//! serde [`Serialize`] and [`Deserialize`] implementations
+//!
+//! A ring buffer is serialized as the sequence of its elements from oldest to newest, so the
+//! slot layout is not part of the format. Deserializing an inline [`RingBuffer`] writes the
+//! elements back from the first slot and fails if there are more than `N`
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use crate::policy::OverflowPolicy;
+use crate::{Cursor, GenericRingBuffer, RingBuffer, Storage};
+
+impl<T, S, P, C> Serialize for GenericRingBuffer<T, S, P, C>
+where
+    T: Serialize,
+    S: Storage<T>,
+    C: Cursor,
+{
+    fn serialize<Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de, T, const N: usize, P, C: Cursor> Deserialize<'de> for RingBuffer<T, N, P, C>
+where
+    T: Deserialize<'de>,
+    P: OverflowPolicy,
+{
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<RingBuffer<T, N, P, C>, D::Error> {
+        deserializer.deserialize_seq(RingBufferVisitor(PhantomData))
+    }
+}
+
+/// Collects a sequence into an inline ring buffer
+struct RingBufferVisitor<T, const N: usize, P, C>(PhantomData<(T, P, C)>);
+
+impl<'de, T, const N: usize, P, C: Cursor> Visitor<'de> for RingBufferVisitor<T, N, P, C>
+where
+    T: Deserialize<'de>,
+    P: OverflowPolicy,
+{
+    type Value = RingBuffer<T, N, P, C>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a sequence of at most {N} elements")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<RingBuffer<T, N, P, C>, A::Error> {
+        let mut ring_buffer = RingBuffer::new();
+        while let Some(data) = seq.next_element()? {
+            if ring_buffer.is_full() {
+                return Err(de::Error::invalid_length(N + 1, &self));
+            }
+
+            ring_buffer.write(data);
+        }
+        Ok(ring_buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RingBuffer;
+    use crate::policy::Reject;
+
+    #[test]
+    fn serialize_fifo_order() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+        ring_buffer.extend([1, 2, 3, 4, 5]);
+
+        assert_eq!(serde_json::to_string(&ring_buffer).unwrap(), "[3,4,5]");
+    }
+
+    #[test]
+    fn deserialize_round_trip() {
+        let mut ring_buffer: RingBuffer<String, 4, Reject> = RingBuffer::new();
+        for word in ["a", "b", "c"] {
+            ring_buffer.write(word.to_string());
+        }
+        ring_buffer.read();
+        ring_buffer.write("d".to_string());
+
+        let json = serde_json::to_string(&ring_buffer).unwrap();
+        let restored: RingBuffer<String, 4, Reject> = serde_json::from_str(&json).unwrap();
+        assert!(restored.iter().eq(ring_buffer.iter()));
+        assert_eq!(restored.as_slices().1, &[] as &[String]);
+    }
+
+    #[test]
+    fn deserialize_too_long() {
+        let error = serde_json::from_str::<RingBuffer<u8, 2>>("[1,2,3]").unwrap_err();
+        assert!(error.to_string().contains("at most 2 elements"));
+    }
+}
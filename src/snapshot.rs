@@ -0,0 +1,246 @@
+//! A compact binary snapshot format for ring buffers
+//!
+//! A snapshot starts with a format version byte, then the encoded size of one element and the
+//! number of elements, both as little-endian `u32`, followed by the elements from oldest to
+//! newest. Elements are encoded through [`Encode`], which needs neither serde nor an allocator
+use crate::policy::OverflowPolicy;
+use crate::{Cursor, GenericRingBuffer, SnapshotError, Storage};
+
+/// The version written to the first byte of every snapshot
+const VERSION: u8 = 1;
+
+/// The size of the version, element size and element count fields
+const HEADER_LEN: usize = 9;
+
+/// An element with a fixed-size binary encoding, used by
+/// [`GenericRingBuffer::dump_to`] and [`GenericRingBuffer::restore_from`]
+///
+/// It is implemented for the primitive integer and float types, encoded as little-endian
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, PartialEq)]
+/// struct Sample {
+///     channel: u8,
+///     value: i16,
+/// }
+///
+/// impl sringbuf::Encode for Sample {
+///     const SIZE: usize = 3;
+///
+///     fn encode(&self, dst: &mut [u8]) {
+///         dst[0] = self.channel;
+///         dst[1..].copy_from_slice(&self.value.to_le_bytes());
+///     }
+///
+///     fn decode(src: &[u8]) -> Sample {
+///         Sample {
+///             channel: src[0],
+///             value: i16::from_le_bytes([src[1], src[2]]),
+///         }
+///     }
+/// }
+///
+/// let mut ring_buffer: sringbuf::RingBuffer<Sample, 4> = sringbuf::RingBuffer::new();
+/// ring_buffer.write(Sample { channel: 1, value: -20 });
+///
+/// let mut bytes = [0; 32];
+/// let len = ring_buffer.dump_to(&mut bytes).unwrap();
+///
+/// let mut restored: sringbuf::RingBuffer<Sample, 4> = sringbuf::RingBuffer::new();
+/// restored.restore_from(&bytes[..len]).unwrap();
+/// assert_eq!(restored.read(), Some(Sample { channel: 1, value: -20 }));
+/// ```
+pub trait Encode: Sized {
+    /// The number of bytes every element encodes to
+    const SIZE: usize;
+
+    /// Encodes an element into `dst`, which is exactly [`Encode::SIZE`] bytes long
+    fn encode(&self, dst: &mut [u8]);
+
+    /// Decodes an element from `src`, which is exactly [`Encode::SIZE`] bytes long
+    fn decode(src: &[u8]) -> Self;
+}
+
+macro_rules! encode_le_bytes {
+    ($($ty:ty),*) => {
+        $(
+            impl Encode for $ty {
+                const SIZE: usize = core::mem::size_of::<$ty>();
+
+                fn encode(&self, dst: &mut [u8]) {
+                    dst.copy_from_slice(&self.to_le_bytes());
+                }
+
+                fn decode(src: &[u8]) -> $ty {
+                    <$ty>::from_le_bytes(src.try_into().unwrap())
+                }
+            }
+        )*
+    };
+}
+
+encode_le_bytes!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64
+);
+
+impl<T, S, P, C> GenericRingBuffer<T, S, P, C>
+where
+    T: Encode,
+    S: Storage<T>,
+    C: Cursor,
+{
+    /// Returns the number of bytes [`GenericRingBuffer::dump_to`] writes for the current
+    /// contents
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u16, 4> = sringbuf::RingBuffer::new();
+    /// ring_buffer.extend([1, 2]);
+    /// assert_eq!(ring_buffer.snapshot_len(), 9 + 2 * 2);
+    /// ```
+    pub fn snapshot_len(&self) -> usize {
+        HEADER_LEN + self.len() * T::SIZE
+    }
+
+    /// Writes a snapshot of a ring buffer to the start of `dst`, returning the number of bytes
+    /// written
+    ///
+    /// Fails with [`SnapshotError::BufferTooSmall`] if `dst` is shorter than
+    /// [`GenericRingBuffer::snapshot_len`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 4> = sringbuf::RingBuffer::new();
+    /// ring_buffer.extend([7, 8]);
+    ///
+    /// let mut bytes = [0; 16];
+    /// assert_eq!(ring_buffer.dump_to(&mut bytes), Ok(11));
+    /// assert_eq!(bytes[..11], [1, 1, 0, 0, 0, 2, 0, 0, 0, 7, 8]);
+    /// ```
+    pub fn dump_to(&self, dst: &mut [u8]) -> Result<usize, SnapshotError> {
+        let len = self.snapshot_len();
+        let element_size = u32::try_from(T::SIZE).map_err(|_| SnapshotError::ElementSize)?;
+        let count = u32::try_from(self.len()).map_err(|_| SnapshotError::TooManyElements)?;
+        if dst.len() < len {
+            return Err(SnapshotError::BufferTooSmall);
+        }
+
+        dst[0] = VERSION;
+        dst[1..5].copy_from_slice(&element_size.to_le_bytes());
+        dst[5..9].copy_from_slice(&count.to_le_bytes());
+        for (index, data) in self.iter().enumerate() {
+            let start = HEADER_LEN + index * T::SIZE;
+            data.encode(&mut dst[start..start + T::SIZE]);
+        }
+        Ok(len)
+    }
+
+    /// Replaces the contents of a ring buffer with the elements of a snapshot
+    ///
+    /// The snapshot is checked before anything is changed, so on error the ring buffer keeps
+    /// its contents. A snapshot holding more elements than the capacity fails with
+    /// [`SnapshotError::TooManyElements`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 4> = sringbuf::RingBuffer::new();
+    /// ring_buffer.restore_from(&[1, 1, 0, 0, 0, 2, 0, 0, 0, 7, 8]).unwrap();
+    /// assert_eq!(ring_buffer.read(), Some(7));
+    /// assert_eq!(ring_buffer.read(), Some(8));
+    /// ```
+    pub fn restore_from(&mut self, src: &[u8]) -> Result<(), SnapshotError>
+    where
+        P: OverflowPolicy,
+    {
+        let header: &[u8; HEADER_LEN] = src
+            .get(..HEADER_LEN)
+            .and_then(|header| header.try_into().ok())
+            .ok_or(SnapshotError::Truncated)?;
+        if header[0] != VERSION {
+            return Err(SnapshotError::UnsupportedVersion(header[0]));
+        }
+
+        let element_size = u32::from_le_bytes([header[1], header[2], header[3], header[4]]);
+        if usize::try_from(element_size) != Ok(T::SIZE) {
+            return Err(SnapshotError::ElementSize);
+        }
+
+        let count = u32::from_le_bytes([header[5], header[6], header[7], header[8]]) as usize;
+        if count > self.capacity() {
+            return Err(SnapshotError::TooManyElements);
+        }
+
+        let records = &src[HEADER_LEN..];
+        if records.len() < count * T::SIZE {
+            return Err(SnapshotError::Truncated);
+        }
+
+        self.clear();
+        for index in 0..count {
+            let start = index * T::SIZE;
+            self.write(T::decode(&records[start..start + T::SIZE]));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{RingBuffer, SnapshotError};
+
+    #[test]
+    fn dump_to_restore_from_wrapped() {
+        let mut ring_buffer: RingBuffer<f32, 3> = RingBuffer::new();
+        ring_buffer.extend([1.5, -2.0, 3.25, 4.0]);
+
+        let mut bytes = [0; 64];
+        let len = ring_buffer.dump_to(&mut bytes).unwrap();
+        assert_eq!(len, 9 + 3 * 4);
+
+        let mut restored: RingBuffer<f32, 5> = RingBuffer::new();
+        restored.extend([9.0; 5]);
+        restored.restore_from(&bytes[..len]).unwrap();
+        assert!(restored.iter().eq(&[-2.0, 3.25, 4.0]));
+    }
+
+    #[test]
+    fn dump_to_buffer_too_small() {
+        let mut ring_buffer: RingBuffer<u32, 2> = RingBuffer::new();
+        ring_buffer.write(1);
+
+        let mut bytes = [0; 12];
+        assert_eq!(
+            ring_buffer.dump_to(&mut bytes),
+            Err(SnapshotError::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn restore_from_invalid_input() {
+        let mut ring_buffer: RingBuffer<u16, 2> = RingBuffer::new();
+        ring_buffer.write(5);
+
+        let cases: [(&[u8], SnapshotError); 5] = [
+            (&[1, 2, 0], SnapshotError::Truncated),
+            (
+                &[2, 2, 0, 0, 0, 0, 0, 0, 0],
+                SnapshotError::UnsupportedVersion(2),
+            ),
+            (&[1, 4, 0, 0, 0, 0, 0, 0, 0], SnapshotError::ElementSize),
+            (&[1, 2, 0, 0, 0, 3, 0, 0, 0], SnapshotError::TooManyElements),
+            (
+                &[1, 2, 0, 0, 0, 2, 0, 0, 0, 1, 0, 2],
+                SnapshotError::Truncated,
+            ),
+        ];
+        for (src, error) in cases {
+            assert_eq!(ring_buffer.restore_from(src), Err(error));
+        }
+        assert_eq!(ring_buffer.read(), Some(5));
+    }
+}
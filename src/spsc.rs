@@ -0,0 +1,1078 @@
+//! A lock-free single-producer single-consumer ring buffer
+//!
+//! [`RingBuffer::split`](crate::RingBuffer::split) turns a ring buffer into a [`Producer`] and a
+//! [`Consumer`] that can be moved to different threads. Each half owns one cursor and only reads
+//! the other, so neither side ever waits on a lock
+//!
+//! Each half can also wait asynchronously, with [`Consumer::read_async`] and
+//! [`Producer::write_async`]. Waiting halves store their task's waker in an [`AtomicWaker`]
+//! slot and the other half wakes it, so the futures run on any executor
+//!
+//! Without an allocator, a [`Queue`] on the stack is split by reference instead, and one placed
+//! in a `static` is split once with [`Queue::try_split`], which suits an interrupt handler
+//! writing to an async task on a microcontroller
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "alloc")] {
+//! let ring_buffer: sringbuf::RingBuffer<u32, 4> = sringbuf::RingBuffer::new();
+//! let (mut producer, mut consumer) = ring_buffer.split();
+//!
+//! let writer = std::thread::spawn(move || {
+//!     for i in 0..100 {
+//!         while producer.try_write(i).is_err() {}
+//!     }
+//! });
+//!
+//! let mut expected = 0;
+//! while expected < 100 {
+//!     if let Some(data) = consumer.read() {
+//!         assert_eq!(data, expected);
+//!         expected += 1;
+//!     }
+//! }
+//! writer.join().unwrap();
+//! # }
+//! ```
+#[cfg(feature = "alloc")]
+use alloc::sync::Arc;
+use core::cell::{Cell, UnsafeCell};
+use core::fmt;
+use core::future;
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+use core::ops::Deref;
+#[cfg(feature = "bytes")]
+use core::ptr;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use core::task::{Context, Poll};
+
+use crate::Full;
+#[cfg(feature = "alloc")]
+use crate::RingBuffer;
+use crate::padding::CachePadded;
+use crate::waker::AtomicWaker;
+
+/// The slots and cursors shared by the two halves of a split ring buffer
+///
+/// A queue can be split by reference with [`Queue::split`], which needs no allocator. `head`
+/// and `tail` count positions modulo `2 * N`, so a full buffer and an empty one have different
+/// cursor distances without needing a separate length
+///
+/// # Examples
+///
+/// ```
+/// let mut queue: sringbuf::spsc::Queue<u8, 4> = sringbuf::spsc::Queue::new();
+/// let (mut producer, mut consumer) = queue.split();
+///
+/// std::thread::scope(|scope| {
+///     scope.spawn(move || producer.try_write(1).unwrap());
+/// });
+/// assert_eq!(consumer.read(), Some(1));
+/// ```
+pub struct Queue<T, const N: usize> {
+    contents: [UnsafeCell<MaybeUninit<T>>; N],
+    head: CachePadded<AtomicUsize>,
+    tail: CachePadded<AtomicUsize>,
+    /// Woken whenever an element is written
+    read_waker: AtomicWaker,
+    /// Woken whenever an element is read
+    write_waker: AtomicWaker,
+    /// Set once either half is dropped
+    closed: AtomicBool,
+    /// Set once `try_split` has handed out the halves
+    taken: AtomicBool,
+}
+
+// SAFETY: the slots are only reached through the halves, which uphold the single-producer
+// single-consumer protocol described on the `Send` impl of `Producer`
+unsafe impl<T: Send, const N: usize> Send for Queue<T, N> {}
+
+// SAFETY: see the `Send` impl
+unsafe impl<T: Send, const N: usize> Sync for Queue<T, N> {}
+
+impl<T, const N: usize> Queue<T, N> {
+    /// Creates a new Queue instance
+    ///
+    /// A capacity of zero is rejected at compile time
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// static QUEUE: sringbuf::spsc::Queue<u8, 16> = sringbuf::spsc::Queue::new();
+    /// assert!(QUEUE.try_split().is_some());
+    /// ```
+    ///
+    /// ```compile_fail
+    /// let queue: sringbuf::spsc::Queue<u8, 0> = sringbuf::spsc::Queue::new();
+    /// ```
+    pub const fn new() -> Queue<T, N> {
+        const { assert!(N > 0, "queue capacity must not be zero") }
+
+        Queue {
+            contents: [const { UnsafeCell::new(MaybeUninit::uninit()) }; N],
+            head: CachePadded::new(AtomicUsize::new(0)),
+            tail: CachePadded::new(AtomicUsize::new(0)),
+            read_waker: AtomicWaker::new(),
+            write_waker: AtomicWaker::new(),
+            closed: AtomicBool::new(false),
+            taken: AtomicBool::new(false),
+        }
+    }
+
+    /// Splits a queue into a [`Producer`] and a [`Consumer`] that borrow it
+    ///
+    /// Elements left over from an earlier split stay queued, and the new halves start open
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut queue: sringbuf::spsc::Queue<u8, 2> = sringbuf::spsc::Queue::new();
+    /// queue.split().0.try_write(1).unwrap();
+    ///
+    /// let (_producer, mut consumer) = queue.split();
+    /// assert_eq!(consumer.read(), Some(1));
+    /// ```
+    pub fn split(&mut self) -> (Producer<'_, T, N>, Consumer<'_, T, N>) {
+        *self.closed.get_mut() = false;
+
+        (
+            Producer {
+                queue: Handle::Borrowed(self),
+            },
+            Consumer {
+                queue: Handle::Borrowed(self),
+                _not_sync: PhantomData,
+            },
+        )
+    }
+
+    /// Splits a shared queue into a [`Producer`] and a [`Consumer`] the first time it is called,
+    /// and returns `None` on every later call
+    ///
+    /// This is how a queue in a `static` is split, since [`split`](Queue::split) needs exclusive
+    /// access. The halves can never be handed out again, so each side keeps a single owner
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// static QUEUE: sringbuf::spsc::Queue<u32, 4> = sringbuf::spsc::Queue::new();
+    ///
+    /// let (mut producer, mut consumer) = QUEUE.try_split().unwrap();
+    /// assert!(QUEUE.try_split().is_none());
+    ///
+    /// // An interrupt handler would own the producer
+    /// std::thread::spawn(move || producer.try_write(7).unwrap())
+    ///     .join()
+    ///     .unwrap();
+    /// assert_eq!(consumer.read(), Some(7));
+    /// ```
+    pub fn try_split(&self) -> Option<(Producer<'_, T, N>, Consumer<'_, T, N>)> {
+        if self.taken.swap(true, Ordering::AcqRel) {
+            return None;
+        }
+        // No other halves exist: earlier ones came from `split`, whose borrow has ended
+        self.closed.store(false, Ordering::Release);
+
+        Some((
+            Producer {
+                queue: Handle::Borrowed(self),
+            },
+            Consumer {
+                queue: Handle::Borrowed(self),
+                _not_sync: PhantomData,
+            },
+        ))
+    }
+
+    /// Returns the number of elements between the cursors `head` and `tail`
+    fn distance(head: usize, tail: usize) -> usize {
+        if tail >= head {
+            tail - head
+        } else {
+            tail + 2 * N - head
+        }
+    }
+
+    /// Returns the cursor following `position`
+    fn advance(position: usize) -> usize {
+        if position + 1 == 2 * N {
+            0
+        } else {
+            position + 1
+        }
+    }
+
+    /// Returns the slot a cursor at `position` points to
+    fn slot(&self, position: usize) -> *mut MaybeUninit<T> {
+        let slot_index = if position >= N {
+            position - N
+        } else {
+            position
+        };
+        self.contents[slot_index].get()
+    }
+
+    /// Returns the cursor `count` positions after `position`, for `count` up to `N`
+    #[cfg(feature = "bytes")]
+    fn advance_by(position: usize, count: usize) -> usize {
+        let position = position + count;
+        if position >= 2 * N {
+            position - 2 * N
+        } else {
+            position
+        }
+    }
+
+    /// Returns the run of up to `count` slots starting at the cursor `position`, cut short at
+    /// the end of the array
+    #[cfg(feature = "bytes")]
+    fn contiguous_slots(&self, position: usize, count: usize) -> *mut [MaybeUninit<T>] {
+        let slot_index = if position >= N {
+            position - N
+        } else {
+            position
+        };
+
+        // SAFETY: `slot_index` is below `N`. The pointer is derived from the whole array, and
+        // `UnsafeCell` has the same layout as its contents, so it covers every slot of the run
+        let first = unsafe { UnsafeCell::raw_get(self.contents.as_ptr().add(slot_index)) };
+        ptr::slice_from_raw_parts_mut(first, count.min(N - slot_index))
+    }
+
+    /// Returns the number of occupied slots; the caller's own cursor cannot move meanwhile, so
+    /// the result never exceeds `N`
+    fn len(&self) -> usize {
+        Queue::<T, N>::distance(
+            self.head.load(Ordering::Acquire),
+            self.tail.load(Ordering::Acquire),
+        )
+    }
+}
+
+impl<T, const N: usize> Drop for Queue<T, N> {
+    /// Drops the elements that were never read
+    fn drop(&mut self) {
+        let mut head = *self.head.get_mut();
+        let tail = *self.tail.get_mut();
+        while head != tail {
+            // SAFETY: both halves are gone and every slot between the cursors holds an element
+            unsafe { (*self.slot(head)).assume_init_drop() };
+            head = Queue::<T, N>::advance(head);
+        }
+    }
+}
+
+impl<T, const N: usize> Default for Queue<T, N> {
+    /// Creates an empty queue
+    fn default() -> Queue<T, N> {
+        Queue::new()
+    }
+}
+
+/// How a half reaches its queue: shared ownership when split from a ring buffer, or a borrow
+/// when split from a [`Queue`]
+enum Handle<'a, T, const N: usize> {
+    #[cfg(feature = "alloc")]
+    Owned(Arc<Queue<T, N>>),
+    Borrowed(&'a Queue<T, N>),
+}
+
+impl<T, const N: usize> Deref for Handle<'_, T, N> {
+    type Target = Queue<T, N>;
+
+    fn deref(&self) -> &Queue<T, N> {
+        match self {
+            #[cfg(feature = "alloc")]
+            Handle::Owned(queue) => queue,
+            Handle::Borrowed(queue) => queue,
+        }
+    }
+}
+
+/// The writing half of a split ring buffer
+pub struct Producer<'a, T, const N: usize> {
+    queue: Handle<'a, T, N>,
+}
+
+/// The reading half of a split ring buffer
+///
+/// A consumer is only `Sync` if `T` is, since [`peek`](Consumer::peek) hands out `&T` to every
+/// thread sharing it
+///
+/// ```compile_fail
+/// fn assert_sync<T: Sync>() {}
+/// assert_sync::<sringbuf::spsc::Consumer<'static, std::cell::Cell<u32>, 4>>();
+/// ```
+pub struct Consumer<'a, T, const N: usize> {
+    queue: Handle<'a, T, N>,
+    _not_sync: PhantomData<Cell<()>>,
+}
+
+// SAFETY: slots are only written by the single producer before it publishes them through
+// `tail`, and only read by the single consumer before it releases them through `head`, so
+// moving either half to another thread only ever moves `T` values across threads
+unsafe impl<T: Send, const N: usize> Send for Producer<'_, T, N> {}
+
+// SAFETY: see the `Send` impl of `Producer`
+unsafe impl<T: Send, const N: usize> Send for Consumer<'_, T, N> {}
+
+// SAFETY: the only shared access to `T` through `&Consumer` is `peek`, and `T: Sync` makes the
+// references it returns safe to use from several threads at once
+unsafe impl<T: Send + Sync, const N: usize> Sync for Consumer<'_, T, N> {}
+
+#[cfg(feature = "alloc")]
+impl<T, const N: usize, P> RingBuffer<T, N, P> {
+    /// Splits a ring buffer into a [`Producer`] and a [`Consumer`] that share its slots
+    ///
+    /// Elements already in the ring buffer stay queued for the consumer. The halves never
+    /// overwrite, so the overflow policy no longer applies
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::RingBuffer<u8, 2> = sringbuf::RingBuffer::new();
+    /// ring_buffer.write(1);
+    ///
+    /// let (mut producer, mut consumer) = ring_buffer.split();
+    /// assert_eq!(producer.try_write(2), Ok(()));
+    /// assert_eq!(producer.try_write(3), Err(sringbuf::Full(3)));
+    /// assert_eq!(consumer.read(), Some(1));
+    /// assert_eq!(consumer.read(), Some(2));
+    /// ```
+    pub fn split(self) -> (Producer<'static, T, N>, Consumer<'static, T, N>) {
+        let queue = Queue::new();
+
+        let mut tail = 0;
+        for data in self {
+            // SAFETY: the queue is not shared yet and the slot is free
+            unsafe { (*queue.slot(tail)).write(data) };
+            tail += 1;
+        }
+        queue.tail.store(tail, Ordering::Relaxed);
+
+        let queue = Arc::new(queue);
+        (
+            Producer {
+                queue: Handle::Owned(Arc::clone(&queue)),
+            },
+            Consumer {
+                queue: Handle::Owned(queue),
+                _not_sync: PhantomData,
+            },
+        )
+    }
+}
+
+impl<T, const N: usize> Producer<'_, T, N> {
+    /// Writes a value to the ring buffer if it has free space, handing the value back in
+    /// [`Full`] otherwise
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut queue: sringbuf::spsc::Queue<u8, 1> = sringbuf::spsc::Queue::new();
+    /// let (mut producer, _consumer) = queue.split();
+    /// assert_eq!(producer.try_write(1), Ok(()));
+    /// assert_eq!(producer.try_write(2), Err(sringbuf::Full(2)));
+    /// ```
+    pub fn try_write(&mut self, data: T) -> Result<(), Full<T>> {
+        let tail = self.queue.tail.load(Ordering::Relaxed);
+        let head = self.queue.head.load(Ordering::Acquire);
+        if Queue::<T, N>::distance(head, tail) == N {
+            return Err(Full(data));
+        }
+
+        // SAFETY: the slot is outside the occupied range, so the consumer does not touch it
+        // until `tail` is advanced past it
+        unsafe { (*self.queue.slot(tail)).write(data) };
+        self.queue
+            .tail
+            .store(Queue::<T, N>::advance(tail), Ordering::Release);
+        self.queue.read_waker.wake();
+        Ok(())
+    }
+
+    /// Copies as many elements from `src` as fit in the free space of the ring buffer,
+    /// returning the number of elements written
+    ///
+    /// The elements are published to the consumer together, with a single cursor update
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut queue: sringbuf::spsc::Queue<u8, 3> = sringbuf::spsc::Queue::new();
+    /// let (mut producer, mut consumer) = queue.split();
+    /// assert_eq!(producer.write_from(&[1, 2, 3, 4]), 3);
+    /// assert_eq!(consumer.read(), Some(1));
+    /// ```
+    pub fn write_from(&mut self, src: &[T]) -> usize
+    where
+        T: Copy,
+    {
+        let mut tail = self.queue.tail.load(Ordering::Relaxed);
+        let head = self.queue.head.load(Ordering::Acquire);
+        let count = src.len().min(N - Queue::<T, N>::distance(head, tail));
+        if count == 0 {
+            return 0;
+        }
+
+        for &data in &src[..count] {
+            // SAFETY: the slot is outside the occupied range, as in `try_write`
+            unsafe { (*self.queue.slot(tail)).write(data) };
+            tail = Queue::<T, N>::advance(tail);
+        }
+
+        self.queue.tail.store(tail, Ordering::Release);
+        self.queue.read_waker.wake();
+        count
+    }
+
+    /// Writes a value to the ring buffer, waiting for free space if it is full
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn block_on<F: Future>(future: F) -> F::Output {
+    /// #     let mut future = std::pin::pin!(future);
+    /// #     let mut cx = std::task::Context::from_waker(std::task::Waker::noop());
+    /// #     loop {
+    /// #         if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+    /// #             return output;
+    /// #         }
+    /// #     }
+    /// # }
+    /// let mut queue: sringbuf::spsc::Queue<u8, 1> = sringbuf::spsc::Queue::new();
+    /// let (mut producer, mut consumer) = queue.split();
+    ///
+    /// block_on(producer.write_async(1));
+    /// assert_eq!(consumer.read(), Some(1));
+    /// ```
+    pub async fn write_async(&mut self, data: T) {
+        future::poll_fn(|cx| self.poll_ready(cx)).await;
+
+        if self.try_write(data).is_err() {
+            unreachable!("only the producer fills slots");
+        }
+    }
+
+    /// Returns `Poll::Ready` once the ring buffer has free space, or registers the task in
+    /// `cx` to be woken when the consumer frees a slot
+    pub fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        if !self.is_full() {
+            return Poll::Ready(());
+        }
+
+        self.queue.write_waker.register(cx.waker());
+
+        // A read may have freed a slot before the waker was registered
+        if self.is_full() {
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    }
+
+    /// Returns the number of elements waiting to be read
+    ///
+    /// The consumer may read concurrently, so the result is only a snapshot
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns `true` if no elements are waiting to be read
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the next write would fail
+    pub fn is_full(&self) -> bool {
+        self.len() == N
+    }
+
+    /// Returns the maximum number of elements the ring buffer can hold
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if the consumer has been dropped or the ring buffer was closed, so
+    /// nothing more should be written
+    pub fn is_closed(&self) -> bool {
+        self.queue.closed.load(Ordering::Acquire)
+    }
+
+    /// Closes the ring buffer without dropping the producer, so the consumer sees the end of
+    /// the data once it has read what is queued
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut queue: sringbuf::spsc::Queue<u8, 2> = sringbuf::spsc::Queue::new();
+    /// let (mut producer, consumer) = queue.split();
+    /// producer.close();
+    /// assert!(consumer.is_closed());
+    /// ```
+    pub fn close(&mut self) {
+        self.queue.closed.store(true, Ordering::Release);
+        self.queue.read_waker.wake();
+    }
+}
+
+impl<T, const N: usize> Consumer<'_, T, N> {
+    /// Reads the oldest element from the ring buffer, or returns `None` if it is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut queue: sringbuf::spsc::Queue<u8, 1> = sringbuf::spsc::Queue::new();
+    /// let (mut producer, mut consumer) = queue.split();
+    /// producer.try_write(1).unwrap();
+    /// assert_eq!(consumer.read(), Some(1));
+    /// assert_eq!(consumer.read(), None);
+    /// ```
+    pub fn read(&mut self) -> Option<T> {
+        let head = self.queue.head.load(Ordering::Relaxed);
+        let tail = self.queue.tail.load(Ordering::Acquire);
+        if head == tail {
+            return None;
+        }
+
+        // SAFETY: the slot is inside the occupied range, so the producer published an element
+        // there and does not touch it until `head` is advanced past it
+        let data = unsafe { (*self.queue.slot(head)).assume_init_read() };
+        self.queue
+            .head
+            .store(Queue::<T, N>::advance(head), Ordering::Release);
+        self.queue.write_waker.wake();
+        Some(data)
+    }
+
+    /// Moves as many of the oldest elements of the ring buffer into `dst` as fit, returning the
+    /// number of elements read
+    ///
+    /// The slots are handed back to the producer together, with a single cursor update
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut queue: sringbuf::spsc::Queue<u8, 3> = sringbuf::spsc::Queue::new();
+    /// let (mut producer, mut consumer) = queue.split();
+    /// producer.write_from(&[1, 2]);
+    /// let mut dst = [0; 4];
+    /// assert_eq!(consumer.read_into(&mut dst), 2);
+    /// assert_eq!(dst, [1, 2, 0, 0]);
+    /// ```
+    pub fn read_into(&mut self, dst: &mut [T]) -> usize
+    where
+        T: Copy,
+    {
+        let mut head = self.queue.head.load(Ordering::Relaxed);
+        let tail = self.queue.tail.load(Ordering::Acquire);
+        let count = dst.len().min(Queue::<T, N>::distance(head, tail));
+        if count == 0 {
+            return 0;
+        }
+
+        for data in &mut dst[..count] {
+            // SAFETY: the slot is inside the occupied range, as in `read`
+            *data = unsafe { (*self.queue.slot(head)).assume_init_read() };
+            head = Queue::<T, N>::advance(head);
+        }
+
+        self.queue.head.store(head, Ordering::Release);
+        self.queue.write_waker.wake();
+        count
+    }
+
+    /// Reads the oldest element from the ring buffer, waiting for one to be written if it is
+    /// empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn block_on<F: Future>(future: F) -> F::Output {
+    /// #     let mut future = std::pin::pin!(future);
+    /// #     let mut cx = std::task::Context::from_waker(std::task::Waker::noop());
+    /// #     loop {
+    /// #         if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+    /// #             return output;
+    /// #         }
+    /// #     }
+    /// # }
+    /// let mut queue: sringbuf::spsc::Queue<u8, 1> = sringbuf::spsc::Queue::new();
+    /// let (mut producer, mut consumer) = queue.split();
+    ///
+    /// producer.try_write(1).unwrap();
+    /// assert_eq!(block_on(consumer.read_async()), 1);
+    /// ```
+    pub async fn read_async(&mut self) -> T {
+        future::poll_fn(|cx| self.poll_read(cx)).await
+    }
+
+    /// Returns `Poll::Ready` once the ring buffer has an element or is closed, or registers the
+    /// task in `cx` to be woken when either happens
+    pub fn poll_read_ready(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        if !self.is_empty() || self.is_closed() {
+            return Poll::Ready(());
+        }
+
+        self.queue.read_waker.register(cx.waker());
+
+        // A write or close may have happened before the waker was registered
+        if !self.is_empty() || self.is_closed() {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+
+    /// Reads the oldest element if there is one, or registers the task in `cx` to be woken
+    /// when the producer writes
+    pub fn poll_read(&mut self, cx: &mut Context<'_>) -> Poll<T> {
+        if let Some(data) = self.read() {
+            return Poll::Ready(data);
+        }
+
+        self.queue.read_waker.register(cx.waker());
+
+        // A write may have landed before the waker was registered
+        match self.read() {
+            Some(data) => Poll::Ready(data),
+            None => Poll::Pending,
+        }
+    }
+
+    /// Returns a reference to the oldest element without reading it, or `None` if the ring
+    /// buffer is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut queue: sringbuf::spsc::Queue<u8, 1> = sringbuf::spsc::Queue::new();
+    /// let (mut producer, consumer) = queue.split();
+    /// producer.try_write(1).unwrap();
+    /// assert_eq!(consumer.peek(), Some(&1));
+    /// ```
+    pub fn peek(&self) -> Option<&T> {
+        let head = self.queue.head.load(Ordering::Relaxed);
+        let tail = self.queue.tail.load(Ordering::Acquire);
+        if head == tail {
+            return None;
+        }
+
+        // SAFETY: as in `read`, and the element stays in place while `self` is borrowed
+        // because only `read` advances `head`
+        Some(unsafe { (*self.queue.slot(head)).assume_init_ref() })
+    }
+
+    /// Returns the number of elements waiting to be read
+    ///
+    /// The producer may write concurrently, so the result is only a snapshot
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns `true` if no elements are waiting to be read
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the ring buffer holds `N` elements
+    pub fn is_full(&self) -> bool {
+        self.len() == N
+    }
+
+    /// Returns the maximum number of elements the ring buffer can hold
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if the producer has been dropped, so no more elements will arrive
+    /// beyond those already queued
+    pub fn is_closed(&self) -> bool {
+        self.queue.closed.load(Ordering::Acquire)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl<T: Copy, const N: usize> Producer<'_, T, N> {
+    /// Returns the free slots that follow the newest element and are contiguous in memory
+    pub(crate) fn writable_slots(&mut self) -> &mut [MaybeUninit<T>] {
+        let tail = self.queue.tail.load(Ordering::Relaxed);
+        let head = self.queue.head.load(Ordering::Acquire);
+        let slots = self
+            .queue
+            .contiguous_slots(tail, N - Queue::<T, N>::distance(head, tail));
+
+        // SAFETY: the slots are outside the occupied range, so the consumer does not touch them
+        // until `tail` is advanced past them, which takes `&mut self`
+        unsafe { &mut *slots }
+    }
+
+    /// Publishes the first `count` slots returned by [`Producer::writable_slots`]
+    ///
+    /// # Safety
+    ///
+    /// Those slots must have been initialized
+    pub(crate) unsafe fn publish(&mut self, count: usize) {
+        assert!(count <= N - self.len());
+
+        let tail = self.queue.tail.load(Ordering::Relaxed);
+        self.queue
+            .tail
+            .store(Queue::<T, N>::advance_by(tail, count), Ordering::Release);
+        self.queue.read_waker.wake();
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl<T: Copy, const N: usize> Consumer<'_, T, N> {
+    /// Returns the oldest elements that are contiguous in memory
+    pub(crate) fn readable_slice(&self) -> &[T] {
+        let head = self.queue.head.load(Ordering::Relaxed);
+        let tail = self.queue.tail.load(Ordering::Acquire);
+        let slots = self
+            .queue
+            .contiguous_slots(head, Queue::<T, N>::distance(head, tail));
+
+        // SAFETY: the slots are inside the occupied range, so they hold published elements that
+        // the producer does not touch until `head` is advanced past them, which takes
+        // `&mut self`
+        unsafe { &*(slots as *const [T]) }
+    }
+
+    /// Hands the oldest `count` slots back to the producer; the elements are `Copy`, so there
+    /// is nothing to drop
+    pub(crate) fn release(&mut self, count: usize) {
+        assert!(count <= self.len());
+
+        let head = self.queue.head.load(Ordering::Relaxed);
+        self.queue
+            .head
+            .store(Queue::<T, N>::advance_by(head, count), Ordering::Release);
+        self.queue.write_waker.wake();
+    }
+}
+
+impl<T, const N: usize> Drop for Producer<'_, T, N> {
+    /// Closes the ring buffer and wakes a consumer waiting for data
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+impl<T, const N: usize> Drop for Consumer<'_, T, N> {
+    /// Closes the ring buffer and wakes a producer waiting for space
+    fn drop(&mut self) {
+        self.queue.closed.store(true, Ordering::Release);
+        self.queue.write_waker.wake();
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for Queue<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Queue")
+            .field("len", &self.len())
+            .field("capacity", &N)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for Producer<'_, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Producer")
+            .field("len", &self.len())
+            .field("capacity", &N)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for Consumer<'_, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Consumer")
+            .field("len", &self.len())
+            .field("capacity", &N)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::spsc::Queue;
+    use crate::{Full, RingBuffer};
+
+    use std::pin::pin;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+    use std::thread::{self, Thread};
+
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<ThreadWaker>) {
+            self.0.unpark();
+        }
+    }
+
+    /// Polls `future` to completion, parking the thread until it is woken
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    #[test]
+    fn split_keeps_contents() {
+        let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+        ring_buffer.extend(1..=4);
+
+        let (mut producer, mut consumer) = ring_buffer.split();
+        assert!(producer.is_full());
+        assert_eq!(producer.try_write(5), Err(Full(5)));
+        assert_eq!(consumer.read(), Some(2));
+        assert_eq!(consumer.read(), Some(3));
+        assert_eq!(consumer.read(), Some(4));
+        assert_eq!(consumer.read(), None);
+    }
+
+    #[test]
+    fn try_write_wrap_around() {
+        let ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+        let (mut producer, mut consumer) = ring_buffer.split();
+
+        for i in 0..10 {
+            assert_eq!(producer.try_write(i), Ok(()));
+            assert_eq!(producer.try_write(i + 100), Ok(()));
+            assert_eq!(consumer.len(), 2);
+            assert_eq!(consumer.peek(), Some(&i));
+            assert_eq!(consumer.read(), Some(i));
+            assert_eq!(consumer.read(), Some(i + 100));
+            assert!(consumer.is_empty());
+        }
+    }
+
+    #[test]
+    fn drop_unread_elements() {
+        let counter = Rc::new(());
+        let ring_buffer: RingBuffer<Rc<()>, 4> = RingBuffer::new();
+        let (mut producer, mut consumer) = ring_buffer.split();
+
+        for _ in 0..3 {
+            producer.try_write(Rc::clone(&counter)).unwrap();
+        }
+        drop(consumer.read());
+        assert_eq!(Rc::strong_count(&counter), 3);
+
+        drop(producer);
+        assert_eq!(Rc::strong_count(&counter), 3);
+        drop(consumer);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn threads_preserve_order() {
+        const COUNT: u32 = if cfg!(miri) { 200 } else { 100_000 };
+
+        let ring_buffer: RingBuffer<u32, 7> = RingBuffer::new();
+        let (mut producer, mut consumer) = ring_buffer.split();
+
+        let writer = thread::spawn(move || {
+            for i in 0..COUNT {
+                let mut data = i;
+                while let Err(Full(rejected)) = producer.try_write(data) {
+                    data = rejected;
+                    thread::yield_now();
+                }
+            }
+        });
+
+        let mut expected = 0;
+        while expected < COUNT {
+            match consumer.read() {
+                Some(data) => {
+                    assert_eq!(data, expected);
+                    expected += 1;
+                }
+                None => thread::yield_now(),
+            }
+        }
+
+        writer.join().unwrap();
+        assert!(consumer.is_empty());
+    }
+
+    #[test]
+    fn poll_read_pending_until_written() {
+        let ring_buffer: RingBuffer<u8, 2> = RingBuffer::new();
+        let (mut producer, mut consumer) = ring_buffer.split();
+        let mut cx = Context::from_waker(Waker::noop());
+
+        assert_eq!(consumer.poll_read(&mut cx), Poll::Pending);
+        producer.try_write(1).unwrap();
+        assert_eq!(consumer.poll_read(&mut cx), Poll::Ready(1));
+    }
+
+    #[test]
+    fn poll_ready_pending_until_read() {
+        let ring_buffer: RingBuffer<u8, 1> = RingBuffer::new();
+        let (mut producer, mut consumer) = ring_buffer.split();
+        let mut cx = Context::from_waker(Waker::noop());
+
+        assert_eq!(producer.poll_ready(&mut cx), Poll::Ready(()));
+        producer.try_write(1).unwrap();
+        assert_eq!(producer.poll_ready(&mut cx), Poll::Pending);
+        consumer.read();
+        assert_eq!(producer.poll_ready(&mut cx), Poll::Ready(()));
+    }
+
+    #[test]
+    fn async_threads_preserve_order() {
+        const COUNT: u32 = if cfg!(miri) { 100 } else { 10_000 };
+
+        let ring_buffer: RingBuffer<u32, 3> = RingBuffer::new();
+        let (mut producer, mut consumer) = ring_buffer.split();
+
+        let writer = thread::spawn(move || {
+            block_on(async {
+                for i in 0..COUNT {
+                    producer.write_async(i).await;
+                }
+            })
+        });
+
+        block_on(async {
+            for i in 0..COUNT {
+                assert_eq!(consumer.read_async().await, i);
+            }
+        });
+        writer.join().unwrap();
+    }
+
+    #[test]
+    fn drop_closes_other_half() {
+        let ring_buffer: RingBuffer<u8, 2> = RingBuffer::new();
+        let (mut producer, consumer) = ring_buffer.split();
+
+        producer.try_write(1).unwrap();
+        assert!(!producer.is_closed());
+        drop(consumer);
+        assert!(producer.is_closed());
+    }
+
+    #[test]
+    fn write_from_read_into_wrap_around() {
+        let ring_buffer: RingBuffer<u8, 4> = RingBuffer::new();
+        let (mut producer, mut consumer) = ring_buffer.split();
+        let mut dst = [0; 3];
+
+        assert_eq!(producer.write_from(&[1, 2, 3]), 3);
+        assert_eq!(consumer.read_into(&mut dst), 3);
+        assert_eq!(producer.write_from(&[4, 5, 6, 7, 8]), 4);
+        assert_eq!(producer.write_from(&[9]), 0);
+        assert_eq!(consumer.read_into(&mut dst), 3);
+        assert_eq!(dst, [4, 5, 6]);
+        assert_eq!(consumer.read(), Some(7));
+        assert_eq!(consumer.read_into(&mut dst), 0);
+    }
+
+    #[test]
+    fn queue_split_borrowed() {
+        let mut queue: Queue<u32, 3> = Queue::new();
+
+        {
+            let (mut producer, mut consumer) = queue.split();
+            thread::scope(|scope| {
+                scope.spawn(move || {
+                    for i in 0..3 {
+                        producer.try_write(i).unwrap();
+                    }
+                });
+            });
+            assert!(consumer.is_closed());
+            assert_eq!(consumer.read(), Some(0));
+        }
+
+        let (producer, mut consumer) = queue.split();
+        assert!(!producer.is_closed());
+        assert_eq!(consumer.read(), Some(1));
+        assert_eq!(consumer.read(), Some(2));
+    }
+
+    #[test]
+    fn queue_async_consumer() {
+        const COUNT: u32 = if cfg!(miri) { 50 } else { 5_000 };
+
+        let mut queue: Queue<u32, 2> = Queue::new();
+        let (mut producer, mut consumer) = queue.split();
+
+        thread::scope(|scope| {
+            scope.spawn(move || {
+                for i in 0..COUNT {
+                    while producer.try_write(i).is_err() {
+                        thread::yield_now();
+                    }
+                }
+            });
+
+            block_on(async {
+                for i in 0..COUNT {
+                    assert_eq!(consumer.read_async().await, i);
+                }
+            });
+        });
+    }
+
+    #[test]
+    fn consumer_peek_shared_across_threads() {
+        fn assert_sync<S: Sync>(_: &S) {}
+
+        let mut queue: Queue<u32, 2> = Queue::new();
+        let (mut producer, consumer) = queue.split();
+        assert_sync(&consumer);
+        producer.try_write(7).unwrap();
+
+        thread::scope(|scope| {
+            for _ in 0..2 {
+                scope.spawn(|| assert_eq!(consumer.peek(), Some(&7)));
+            }
+        });
+    }
+
+    #[test]
+    fn queue_try_split_once() {
+        static QUEUE: Queue<u32, 2> = Queue::new();
+
+        let (mut producer, mut consumer) = QUEUE.try_split().unwrap();
+        assert!(QUEUE.try_split().is_none());
+
+        thread::scope(|scope| {
+            scope.spawn(move || {
+                for i in 0..10 {
+                    while producer.try_write(i).is_err() {
+                        thread::yield_now();
+                    }
+                }
+            });
+
+            for i in 0..10 {
+                loop {
+                    if let Some(data) = consumer.read() {
+                        assert_eq!(data, i);
+                        break;
+                    }
+                    thread::yield_now();
+                }
+            }
+        });
+        assert!(consumer.is_closed());
+    }
+}
@@ -0,0 +1,228 @@
+//! A ring buffer that counts its traffic, losses and peak occupancy
+use core::ops::Deref;
+
+use crate::RingBuffer;
+use crate::policy::sealed::Overflow;
+use crate::policy::{OverflowPolicy, Overwrite};
+
+/// Counters collected by a [`StatsRingBuffer`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Stats {
+    /// The number of writes attempted, including those that were rejected
+    pub writes: u64,
+    /// The number of elements read
+    pub reads: u64,
+    /// The number of unread elements overwritten by newer ones
+    pub overwritten: u64,
+    /// The number of writes rejected because the ring buffer was full
+    pub rejected: u64,
+    /// The largest number of elements held at once
+    pub high_watermark: usize,
+}
+
+/// How a write to a ring buffer went, as counted by the instrumented ring buffers
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum WriteOutcome {
+    /// The element was stored in a free slot
+    Stored,
+    /// The element was stored over the oldest unread one
+    Overwritten,
+    /// The element was handed back because the ring buffer was full
+    Rejected,
+}
+
+impl WriteOutcome {
+    /// Writes data to a ring buffer, returning what the write returned and how it went
+    pub(crate) fn write<T, const N: usize, P: OverflowPolicy>(
+        ring_buffer: &mut RingBuffer<T, N, P>,
+        data: T,
+    ) -> (Option<T>, WriteOutcome) {
+        let outcome = if !ring_buffer.is_full() {
+            WriteOutcome::Stored
+        } else {
+            match P::OVERFLOW {
+                Overflow::Overwrite => WriteOutcome::Overwritten,
+                Overflow::Reject => WriteOutcome::Rejected,
+                // The write below panics
+                Overflow::Panic => WriteOutcome::Stored,
+            }
+        };
+        (ring_buffer.write(data), outcome)
+    }
+}
+
+/// A FIFO ring buffer that records [`Stats`] about its use, so its capacity can be sized from
+/// real traffic
+#[derive(Clone, Debug)]
+pub struct StatsRingBuffer<T, const N: usize, P = Overwrite> {
+    ring_buffer: RingBuffer<T, N, P>,
+    stats: Stats,
+}
+
+impl<T, const N: usize, P> StatsRingBuffer<T, N, P> {
+    /// Creates a new, empty StatsRingBuffer instance with every counter at zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let ring_buffer: sringbuf::StatsRingBuffer<u8, 5> = sringbuf::StatsRingBuffer::new();
+    /// assert_eq!(ring_buffer.stats(), sringbuf::Stats::default());
+    /// ```
+    pub const fn new() -> StatsRingBuffer<T, N, P> {
+        StatsRingBuffer {
+            ring_buffer: RingBuffer::new(),
+            stats: Stats {
+                writes: 0,
+                reads: 0,
+                overwritten: 0,
+                rejected: 0,
+                high_watermark: 0,
+            },
+        }
+    }
+
+    /// Writes data to a ring buffer, counting the write and what it displaced
+    ///
+    /// The overflow policy `P` applies as for
+    /// [`GenericRingBuffer::write`](crate::GenericRingBuffer::write), and the element that did
+    /// not make it into the ring buffer is returned
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::StatsRingBuffer<u8, 2> = sringbuf::StatsRingBuffer::new();
+    /// ring_buffer.extend([1, 2, 3]);
+    /// ring_buffer.read();
+    ///
+    /// let stats = ring_buffer.stats();
+    /// assert_eq!((stats.writes, stats.reads, stats.overwritten), (3, 1, 1));
+    /// assert_eq!(stats.high_watermark, 2);
+    /// ```
+    pub fn write(&mut self, data: T) -> Option<T>
+    where
+        P: OverflowPolicy,
+    {
+        let (displaced, outcome) = WriteOutcome::write(&mut self.ring_buffer, data);
+
+        self.stats.writes += 1;
+        match outcome {
+            WriteOutcome::Stored => {}
+            WriteOutcome::Overwritten => self.stats.overwritten += 1,
+            WriteOutcome::Rejected => self.stats.rejected += 1,
+        }
+        self.stats.high_watermark = self.stats.high_watermark.max(self.ring_buffer.len());
+        displaced
+    }
+
+    /// Reads the oldest element from a ring buffer, or returns `None` if it is empty
+    pub fn read(&mut self) -> Option<T> {
+        let data = self.ring_buffer.read();
+        if data.is_some() {
+            self.stats.reads += 1;
+        }
+        data
+    }
+
+    /// Returns the counters collected so far
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// Resets every counter, returning their previous values
+    ///
+    /// The high watermark restarts from the current length
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ring_buffer: sringbuf::StatsRingBuffer<u8, 4> = sringbuf::StatsRingBuffer::new();
+    /// ring_buffer.extend([1, 2, 3]);
+    /// ring_buffer.read();
+    /// assert_eq!(ring_buffer.take_stats().high_watermark, 3);
+    /// assert_eq!(ring_buffer.stats().high_watermark, 2);
+    /// ```
+    pub fn take_stats(&mut self) -> Stats {
+        let stats = self.stats;
+        self.stats = Stats {
+            high_watermark: self.ring_buffer.len(),
+            ..Stats::default()
+        };
+        stats
+    }
+
+    /// Drops every element of a ring buffer without counting them as read
+    pub fn clear(&mut self) {
+        self.ring_buffer.clear();
+    }
+
+    /// Returns the underlying ring buffer, dropping the counters
+    pub fn into_inner(self) -> RingBuffer<T, N, P> {
+        self.ring_buffer
+    }
+}
+
+impl<T, const N: usize, P> Deref for StatsRingBuffer<T, N, P> {
+    type Target = RingBuffer<T, N, P>;
+
+    fn deref(&self) -> &RingBuffer<T, N, P> {
+        &self.ring_buffer
+    }
+}
+
+impl<T, const N: usize, P> Default for StatsRingBuffer<T, N, P> {
+    /// Creates an empty ring buffer
+    fn default() -> StatsRingBuffer<T, N, P> {
+        StatsRingBuffer::new()
+    }
+}
+
+impl<T, const N: usize, P: OverflowPolicy> Extend<T> for StatsRingBuffer<T, N, P> {
+    /// Writes every element of an iterator to a ring buffer
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for data in iter {
+            self.write(data);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::policy::Reject;
+    use crate::{Stats, StatsRingBuffer};
+
+    #[test]
+    fn stats_overwrite_policy() {
+        let mut ring_buffer: StatsRingBuffer<u8, 3> = StatsRingBuffer::new();
+
+        ring_buffer.extend(0..5);
+        ring_buffer.read();
+        ring_buffer.read();
+        ring_buffer.write(5);
+        while ring_buffer.read().is_some() {}
+
+        assert_eq!(
+            ring_buffer.stats(),
+            Stats {
+                writes: 6,
+                reads: 4,
+                overwritten: 2,
+                rejected: 0,
+                high_watermark: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn stats_reject_policy() {
+        let mut ring_buffer: StatsRingBuffer<u8, 2, Reject> = StatsRingBuffer::new();
+
+        ring_buffer.extend(0..5);
+        ring_buffer.clear();
+        ring_buffer.write(5);
+
+        let stats = ring_buffer.stats();
+        assert_eq!((stats.writes, stats.reads), (6, 0));
+        assert_eq!((stats.overwritten, stats.rejected), (0, 3));
+        assert_eq!(stats.high_watermark, 2);
+    }
+}
@@ -0,0 +1,173 @@
+//! Backing storage for [`GenericRingBuffer`](crate::GenericRingBuffer)
+use core::mem::MaybeUninit;
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
+/// A fixed-length run of slots that a ring buffer stores its elements in
+///
+/// This trait is sealed. It is implemented for inline arrays, which back
+/// [`RingBuffer`](crate::RingBuffer), for borrowed slices, which let a ring buffer live in memory
+/// owned by the caller, and, with the `alloc` feature, for boxed slices, which back
+/// [`HeapRingBuffer`](crate::HeapRingBuffer). Borrowed slices of initialized `Copy` values back
+/// [`RingBufferSlice`](crate::RingBufferSlice)
+pub trait Storage<T>: sealed::Sealed<T> {
+    /// Returns the slots as a slice
+    fn as_slice(&self) -> &[MaybeUninit<T>];
+
+    /// Returns the slots as a mutable slice
+    fn as_mut_slice(&mut self) -> &mut [MaybeUninit<T>];
+}
+
+/// A [`Storage`] whose slots may be left uninitialized, so they can be handed out to be filled
+/// in place
+///
+/// This trait is sealed. It is implemented by every storage except the initialized slices behind
+/// [`RingBufferSlice`](crate::RingBufferSlice), which must stay initialized for their owner
+pub trait UninitStorage<T>: Storage<T> + sealed::Uninit<T> {}
+
+impl<T, const N: usize> UninitStorage<T> for [MaybeUninit<T>; N] {}
+
+impl<T> UninitStorage<T> for &mut [MaybeUninit<T>] {}
+
+#[cfg(feature = "alloc")]
+impl<T> UninitStorage<T> for Box<[MaybeUninit<T>]> {}
+
+impl<T, const N: usize> Storage<T> for [MaybeUninit<T>; N] {
+    fn as_slice(&self) -> &[MaybeUninit<T>] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [MaybeUninit<T>] {
+        self
+    }
+}
+
+impl<T> Storage<T> for &mut [MaybeUninit<T>] {
+    fn as_slice(&self) -> &[MaybeUninit<T>] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [MaybeUninit<T>] {
+        self
+    }
+}
+
+impl<T: Copy> Storage<T> for &mut [T] {
+    fn as_slice(&self) -> &[MaybeUninit<T>] {
+        // SAFETY: `MaybeUninit<T>` has the same layout as `T`, and a shared slice cannot be used
+        // to de-initialize anything
+        unsafe { &*(&**self as *const [T] as *const [MaybeUninit<T>]) }
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [MaybeUninit<T>] {
+        // SAFETY: `MaybeUninit<T>` has the same layout as `T`. This storage is not an
+        // `UninitStorage`, so no safe method hands its slots out, and the ring buffer only ever
+        // stores initialized values into them: the slice is still fully initialized when the
+        // borrow ends. `T: Copy` means moving elements out of a slot leaves nothing to double drop
+        unsafe { &mut *(&mut **self as *mut [T] as *mut [MaybeUninit<T>]) }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Storage<T> for Box<[MaybeUninit<T>]> {
+    fn as_slice(&self) -> &[MaybeUninit<T>] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [MaybeUninit<T>] {
+        self
+    }
+}
+
+pub(crate) mod sealed {
+    use core::mem::MaybeUninit;
+
+    #[cfg(feature = "alloc")]
+    use alloc::boxed::Box;
+
+    pub trait Sealed<T> {
+        /// The name of the ring buffer alias over this storage, as shown by the formatting
+        /// implementations
+        const NAME: &'static str;
+    }
+
+    impl<T, const N: usize> Sealed<T> for [MaybeUninit<T>; N] {
+        const NAME: &'static str = "RingBuffer";
+    }
+
+    impl<T> Sealed<T> for &mut [MaybeUninit<T>] {
+        const NAME: &'static str = "RingBufferView";
+    }
+
+    impl<T: Copy> Sealed<T> for &mut [T] {
+        const NAME: &'static str = "RingBufferSlice";
+    }
+
+    pub trait Uninit<T> {}
+
+    impl<T, const N: usize> Uninit<T> for [MaybeUninit<T>; N] {}
+
+    impl<T> Uninit<T> for &mut [MaybeUninit<T>] {}
+
+    #[cfg(feature = "alloc")]
+    impl<T> Uninit<T> for Box<[MaybeUninit<T>]> {}
+
+    #[cfg(feature = "alloc")]
+    impl<T> Sealed<T> for Box<[MaybeUninit<T>]> {
+        const NAME: &'static str = "HeapRingBuffer";
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::GenericRingBuffer;
+    use crate::policy::Reject;
+
+    use core::mem::MaybeUninit;
+    use std::rc::Rc;
+
+    #[test]
+    fn borrowed_slice_wrap_around() {
+        let mut slots = [const { MaybeUninit::uninit() }; 3];
+        let mut ring_buffer: GenericRingBuffer<u8, &mut [MaybeUninit<u8>]> =
+            GenericRingBuffer::from_storage(&mut slots[..]);
+
+        ring_buffer.extend(1..=5);
+        assert_eq!(ring_buffer.as_slices(), (&[3][..], &[4, 5][..]));
+        assert_eq!(ring_buffer.read(), Some(3));
+        assert_eq!(ring_buffer.len(), 2);
+    }
+
+    #[test]
+    fn borrowed_slice_policy() {
+        let mut slots = [const { MaybeUninit::uninit() }; 1];
+        let mut ring_buffer: GenericRingBuffer<u8, &mut [MaybeUninit<u8>], Reject> =
+            GenericRingBuffer::from_storage(&mut slots[..]);
+
+        assert_eq!(ring_buffer.write(1), None);
+        assert_eq!(ring_buffer.write(2), Some(2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_storage_empty() {
+        let mut slots: [MaybeUninit<u8>; 0] = [];
+        let _ring_buffer: GenericRingBuffer<u8, &mut [MaybeUninit<u8>]> =
+            GenericRingBuffer::from_storage(&mut slots[..]);
+    }
+
+    #[test]
+    fn borrowed_slice_drops_elements() {
+        let counter = Rc::new(());
+        let mut slots = [const { MaybeUninit::uninit() }; 2];
+        let mut ring_buffer: GenericRingBuffer<Rc<()>, &mut [MaybeUninit<Rc<()>>]> =
+            GenericRingBuffer::from_storage(&mut slots[..]);
+
+        ring_buffer.extend(std::iter::repeat_with(|| Rc::clone(&counter)).take(3));
+        assert_eq!(Rc::strong_count(&counter), 3);
+
+        drop(ring_buffer);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+}
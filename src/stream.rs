@@ -0,0 +1,128 @@
+//! [`Stream`] and [`Sink`] implementations for the halves of a split ring buffer
+use core::convert::Infallible;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_core::Stream;
+use futures_sink::Sink;
+
+use crate::spsc::{Consumer, Producer};
+
+impl<T, const N: usize> Stream for Consumer<'_, T, N> {
+    type Item = T;
+
+    /// Reads the oldest element, ending the stream once the producer is dropped and every
+    /// queued element has been read
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let consumer = self.get_mut();
+        match consumer.poll_read(cx) {
+            Poll::Ready(data) => Poll::Ready(Some(data)),
+            // The producer may have written its last element just before closing
+            Poll::Pending if consumer.is_closed() => Poll::Ready(consumer.read()),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), None)
+    }
+}
+
+impl<T, const N: usize> Sink<T> for Producer<'_, T, N> {
+    type Error = Infallible;
+
+    /// Waits for free space in the ring buffer
+    ///
+    /// Once the consumer is dropped the sink is always ready and discards what it is sent,
+    /// since nothing could read it
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        let producer = self.get_mut();
+        if producer.is_closed() {
+            return Poll::Ready(Ok(()));
+        }
+
+        Producer::poll_ready(producer, cx).map(Ok)
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Infallible> {
+        // Only fails once the consumer is gone, in which case the element is discarded
+        let _ = self.get_mut().try_write(item);
+        Ok(())
+    }
+
+    /// Elements are readable as soon as they are sent, so there is nothing to flush
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        Poll::Ready(Ok(()))
+    }
+
+    /// Closes the ring buffer, which ends the consumer's stream once it is drained
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        self.get_mut().close();
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RingBuffer;
+
+    use futures::executor::block_on;
+    use futures::{SinkExt, StreamExt, stream};
+    use std::thread;
+
+    #[test]
+    fn stream_ends_after_producer_drop() {
+        let ring_buffer: RingBuffer<u8, 4> = RingBuffer::new();
+        let (mut producer, consumer) = ring_buffer.split();
+
+        producer.try_write(1).unwrap();
+        producer.try_write(2).unwrap();
+        drop(producer);
+
+        assert_eq!(block_on(consumer.collect::<Vec<_>>()), [1, 2]);
+    }
+
+    #[test]
+    fn sink_send_all() {
+        let ring_buffer: RingBuffer<u32, 2> = RingBuffer::new();
+        let (mut producer, consumer) = ring_buffer.split();
+
+        let writer = thread::spawn(move || {
+            block_on(producer.send_all(&mut stream::iter(0..100).map(Ok))).unwrap();
+        });
+
+        let received = block_on(consumer.map(|data| data * 2).collect::<Vec<_>>());
+        writer.join().unwrap();
+        assert!(received.into_iter().eq((0..100).map(|data| data * 2)));
+    }
+
+    #[test]
+    fn forward_between_ring_buffers() {
+        let first: RingBuffer<u8, 8> = RingBuffer::new();
+        let second: RingBuffer<u8, 8> = RingBuffer::new();
+        let (mut first_producer, first_consumer) = first.split();
+        let (second_producer, second_consumer) = second.split();
+
+        for i in 0..5 {
+            first_producer.try_write(i).unwrap();
+        }
+        drop(first_producer);
+
+        block_on(first_consumer.map(Ok).forward(second_producer)).unwrap();
+        assert_eq!(
+            block_on(second_consumer.collect::<Vec<_>>()),
+            [0, 1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn sink_discards_after_consumer_drop() {
+        let ring_buffer: RingBuffer<u8, 1> = RingBuffer::new();
+        let (mut producer, consumer) = ring_buffer.split();
+
+        drop(consumer);
+        block_on(producer.send(1)).unwrap();
+        block_on(producer.send(2)).unwrap();
+        assert!(producer.is_full());
+    }
+}
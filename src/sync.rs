@@ -0,0 +1,12 @@
+//! Locking shared by the std-backed queues
+
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// Locks a mutex, taking the guard even if another thread panicked while holding it
+///
+/// Every mutex in this crate guards a ring buffer or the bookkeeping around one, which is only
+/// changed by operations that cannot panic halfway through, so a poisoned lock still guards
+/// consistent state. The same holds for the guards handed back by condition variable waits
+pub(crate) fn lock_ignoring_poison<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
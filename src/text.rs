@@ -0,0 +1,142 @@
+//! [`fmt::Write`] implementations for byte and char ring buffers
+use core::fmt;
+
+use crate::policy::OverflowPolicy;
+use crate::policy::sealed::Overflow;
+use crate::{Cursor, GenericRingBuffer, Storage};
+
+impl<S, P, C> fmt::Write for GenericRingBuffer<u8, S, P, C>
+where
+    S: Storage<u8>,
+    C: Cursor,
+    P: OverflowPolicy,
+{
+    /// Writes the UTF-8 encoding of `s`, keeping the contents starting on a character boundary
+    ///
+    /// When overwriting, the bytes left over from a partly overwritten character are dropped
+    /// along with it. Otherwise only the whole characters that fit are written, and the write
+    /// fails if any did not
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        match P::OVERFLOW {
+            Overflow::Overwrite => {
+                let bytes = s.as_bytes();
+                let start = bytes.len().saturating_sub(self.capacity());
+                for &byte in &bytes[start..] {
+                    self.write(byte);
+                }
+
+                while self.peek().is_some_and(|&byte| is_continuation(byte)) {
+                    self.read();
+                }
+                Ok(())
+            }
+            Overflow::Reject | Overflow::Panic => {
+                let mut end = s.len().min(self.remaining());
+                while !s.is_char_boundary(end) {
+                    end -= 1;
+                }
+
+                if end < s.len() && matches!(P::OVERFLOW, Overflow::Panic) {
+                    panic!("write to a full ring buffer");
+                }
+
+                self.write_from(&s.as_bytes()[..end]);
+                if end == s.len() {
+                    Ok(())
+                } else {
+                    Err(fmt::Error)
+                }
+            }
+        }
+    }
+}
+
+impl<S, P, C> fmt::Write for GenericRingBuffer<char, S, P, C>
+where
+    S: Storage<char>,
+    C: Cursor,
+    P: OverflowPolicy,
+{
+    /// Writes the characters of `s`, one per slot, failing if the policy rejected any of them
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            self.write_char(c)?;
+        }
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        match self.write(c) {
+            Some(_) if !matches!(P::OVERFLOW, Overflow::Overwrite) => Err(fmt::Error),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Returns `true` if `byte` continues a multi-byte UTF-8 sequence rather than starting one
+fn is_continuation(byte: u8) -> bool {
+    byte & 0b1100_0000 == 0b1000_0000
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RingBuffer;
+    use crate::policy::{Panic, Reject};
+
+    use core::fmt::Write;
+
+    fn contents(ring_buffer: &RingBuffer<u8, 4, impl Sized>) -> String {
+        String::from_utf8(ring_buffer.iter().copied().collect()).unwrap()
+    }
+
+    #[test]
+    fn write_str_bytes() {
+        let mut ring_buffer: RingBuffer<u8, 4> = RingBuffer::new();
+
+        write!(ring_buffer, "{}", 12).unwrap();
+        assert_eq!(contents(&ring_buffer), "12");
+        write!(ring_buffer, "{}", 345).unwrap();
+        assert_eq!(contents(&ring_buffer), "2345");
+    }
+
+    #[test]
+    fn write_str_overwrite_char_boundary() {
+        let mut ring_buffer: RingBuffer<u8, 4> = RingBuffer::new();
+
+        write!(ring_buffer, "é").unwrap();
+        write!(ring_buffer, "abc").unwrap();
+        assert_eq!(contents(&ring_buffer), "abc");
+
+        write!(ring_buffer, "a€€").unwrap();
+        assert_eq!(contents(&ring_buffer), "€");
+        assert_eq!(ring_buffer.len(), 3);
+    }
+
+    #[test]
+    fn write_str_reject_whole_chars() {
+        let mut ring_buffer: RingBuffer<u8, 4, Reject> = RingBuffer::new();
+
+        assert!(write!(ring_buffer, "ab€").is_err());
+        assert_eq!(contents(&ring_buffer), "ab");
+        assert!(write!(ring_buffer, "cd").is_ok());
+        assert_eq!(contents(&ring_buffer), "abcd");
+    }
+
+    #[test]
+    #[should_panic]
+    fn write_str_panic_full() {
+        let mut ring_buffer: RingBuffer<u8, 4, Panic> = RingBuffer::new();
+        let _ = write!(ring_buffer, "abcde");
+    }
+
+    #[test]
+    fn write_char_slots() {
+        let mut ring_buffer: RingBuffer<char, 3> = RingBuffer::new();
+        write!(ring_buffer, "añ€x").unwrap();
+        assert!(ring_buffer.iter().eq(&['ñ', '€', 'x']));
+
+        let mut ring_buffer: RingBuffer<char, 3, Reject> = RingBuffer::new();
+        assert!(write!(ring_buffer, "añ€x").is_err());
+        assert!(ring_buffer.iter().eq(&['a', 'ñ', '€']));
+    }
+}
@@ -0,0 +1,285 @@
+//! A ring buffer whose entries expire a fixed time after they are written
+//!
+//! This bounds a ring buffer by age as well as by count, keeping for instance the last five
+//! seconds of samples: reads and iterators only expose the entries inside that sliding window.
+//! Time comes from a [`Clock`], which only has to count ticks in some monotonic unit, so the
+//! buffer works on `no_std` targets with a hardware timer as well as with the standard library
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::Instant;
+//!
+//! let start = Instant::now();
+//! let clock = move || start.elapsed().as_millis() as u64;
+//! let mut ring_buffer: sringbuf::TimedRingBuffer<u8, 8, _> =
+//!     sringbuf::TimedRingBuffer::new(5_000, clock);
+//! ring_buffer.write(1);
+//! assert_eq!(ring_buffer.read(), Some(1));
+//! ```
+use core::iter::FusedIterator;
+
+use crate::RingBuffer;
+use crate::iter::Iter as RingIter;
+use crate::policy::{OverflowPolicy, Overwrite};
+
+/// A monotonic source of timestamps, in ticks of any unit
+///
+/// This trait is implemented by every closure returning a `u64`
+pub trait Clock {
+    /// Returns the current time, which must never go backwards
+    fn now(&self) -> u64;
+}
+
+impl<F: Fn() -> u64> Clock for F {
+    fn now(&self) -> u64 {
+        self()
+    }
+}
+
+/// A FIFO ring buffer that stamps every write with the time of a [`Clock`] and expires entries
+/// once they are `ttl` ticks old
+///
+/// Expired entries are skipped lazily: reads, [`peek`](TimedRingBuffer::peek) and
+/// [`len`](TimedRingBuffer::len) only see entries younger than the time to live
+#[derive(Clone, Debug)]
+pub struct TimedRingBuffer<T, const N: usize, K, P = Overwrite> {
+    ring_buffer: RingBuffer<(u64, T), N, P>,
+    ttl: u64,
+    clock: K,
+}
+
+impl<T, const N: usize, K: Clock, P> TimedRingBuffer<T, N, K, P> {
+    /// Creates a new, empty TimedRingBuffer instance whose entries live for `ttl` ticks of
+    /// `clock`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let ring_buffer: sringbuf::TimedRingBuffer<u8, 4, _> =
+    ///     sringbuf::TimedRingBuffer::new(100, || 0);
+    /// assert_eq!(ring_buffer.ttl(), 100);
+    /// ```
+    pub const fn new(ttl: u64, clock: K) -> TimedRingBuffer<T, N, K, P> {
+        TimedRingBuffer {
+            ring_buffer: RingBuffer::new(),
+            ttl,
+            clock,
+        }
+    }
+
+    /// Writes data to a ring buffer, stamped with the current time
+    ///
+    /// Expired entries are dropped first, so their slots are reclaimed before any live entry is
+    /// displaced. If the ring buffer is still full, the overflow policy `P` applies as for
+    /// [`GenericRingBuffer::write`](crate::GenericRingBuffer::write), and the element that did
+    /// not make it into the ring buffer is returned
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cell::Cell;
+    /// use sringbuf::policy::Reject;
+    ///
+    /// let now = Cell::new(0);
+    /// let mut ring_buffer: sringbuf::TimedRingBuffer<u8, 1, _, Reject> =
+    ///     sringbuf::TimedRingBuffer::new(10, || now.get());
+    /// assert_eq!(ring_buffer.write(1), None);
+    /// assert_eq!(ring_buffer.write(2), Some(2));
+    /// now.set(10);
+    /// assert_eq!(ring_buffer.write(3), None);
+    /// ```
+    pub fn write(&mut self, data: T) -> Option<T>
+    where
+        P: OverflowPolicy,
+    {
+        self.expire();
+        let now = self.clock.now();
+        self.ring_buffer.write((now, data)).map(|(_, data)| data)
+    }
+
+    /// Reads the oldest entry that has not expired, dropping the expired ones before it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cell::Cell;
+    ///
+    /// let now = Cell::new(0);
+    /// let mut ring_buffer: sringbuf::TimedRingBuffer<u8, 4, _> =
+    ///     sringbuf::TimedRingBuffer::new(10, || now.get());
+    /// ring_buffer.write(1);
+    /// now.set(5);
+    /// ring_buffer.write(2);
+    /// now.set(12);
+    /// assert_eq!(ring_buffer.read(), Some(2));
+    /// ```
+    pub fn read(&mut self) -> Option<T> {
+        self.expire();
+        self.ring_buffer.read().map(|(_, data)| data)
+    }
+
+    /// Returns a reference to the oldest entry that has not expired, dropping the expired ones
+    /// before it
+    pub fn peek(&mut self) -> Option<&T> {
+        self.expire();
+        self.ring_buffer.peek().map(|(_, data)| data)
+    }
+
+    /// Returns an iterator over the entries that have not expired, from oldest to newest
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cell::Cell;
+    ///
+    /// let now = Cell::new(0);
+    /// let mut ring_buffer: sringbuf::TimedRingBuffer<u8, 4, _> =
+    ///     sringbuf::TimedRingBuffer::new(5_000, || now.get());
+    /// ring_buffer.write(1);
+    /// now.set(3_000);
+    /// ring_buffer.write(2);
+    /// now.set(6_000);
+    /// assert!(ring_buffer.iter().eq(&[2]));
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        let mut iter = self.ring_buffer.iter();
+        if let Some(expired) = self.expired().checked_sub(1) {
+            iter.nth(expired);
+        }
+        Iter { iter }
+    }
+
+    /// Drops every expired entry, returning how many were dropped
+    pub fn expire(&mut self) -> usize {
+        let expired = self.expired();
+        self.ring_buffer.advance_read(expired)
+    }
+
+    /// Returns the number of entries that have not expired
+    pub fn len(&self) -> usize {
+        self.ring_buffer.len() - self.expired()
+    }
+
+    /// Returns `true` if every entry has expired
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of ticks an entry lives for
+    pub fn ttl(&self) -> u64 {
+        self.ttl
+    }
+
+    /// Drops every entry of a ring buffer
+    pub fn clear(&mut self) {
+        self.ring_buffer.clear();
+    }
+
+    /// Returns the underlying ring buffer of timestamped entries, expired ones included
+    pub fn into_inner(self) -> RingBuffer<(u64, T), N, P> {
+        self.ring_buffer
+    }
+
+    /// Returns the number of expired entries at the front, which are the oldest since the clock
+    /// is monotonic
+    fn expired(&self) -> usize {
+        let now = self.clock.now();
+        self.ring_buffer
+            .position(|(stamp, _)| now.saturating_sub(*stamp) < self.ttl)
+            .unwrap_or(self.ring_buffer.len())
+    }
+}
+
+/// An iterator over the entries of a timed ring buffer that have not expired, from oldest to
+/// newest
+///
+/// This struct is created by [`TimedRingBuffer::iter`]
+#[derive(Clone, Debug)]
+pub struct Iter<'a, T> {
+    iter: RingIter<'a, (u64, T)>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.iter.next().map(|(_, data)| data)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.iter.next_back().map(|(_, data)| data)
+    }
+}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::TimedRingBuffer;
+
+    use std::cell::Cell;
+
+    #[test]
+    fn read_skips_expired() {
+        let now = Cell::new(0);
+        let mut ring_buffer: TimedRingBuffer<char, 4, _> = TimedRingBuffer::new(10, || now.get());
+
+        ring_buffer.write('a');
+        now.set(3);
+        ring_buffer.write('b');
+        ring_buffer.write('c');
+        assert_eq!(ring_buffer.len(), 3);
+
+        now.set(10);
+        assert_eq!(ring_buffer.len(), 2);
+        assert_eq!(ring_buffer.peek(), Some(&'b'));
+        assert_eq!(ring_buffer.read(), Some('b'));
+
+        now.set(13);
+        assert!(ring_buffer.is_empty());
+        assert_eq!(ring_buffer.read(), None);
+    }
+
+    #[test]
+    fn expire_counts_dropped() {
+        let now = Cell::new(0);
+        let mut ring_buffer: TimedRingBuffer<u8, 4, _> = TimedRingBuffer::new(5, || now.get());
+
+        ring_buffer.write(1);
+        ring_buffer.write(2);
+        now.set(2);
+        ring_buffer.write(3);
+        now.set(6);
+        assert_eq!(ring_buffer.expire(), 2);
+        assert_eq!(ring_buffer.expire(), 0);
+        assert!(ring_buffer.into_inner().iter().eq(&[(2, 3)]));
+    }
+
+    #[test]
+    fn iter_sliding_window() {
+        let now = Cell::new(0);
+        let mut ring_buffer: TimedRingBuffer<u32, 8, _> = TimedRingBuffer::new(3, || now.get());
+
+        for tick in 0..6 {
+            now.set(tick);
+            ring_buffer.write(tick as u32);
+        }
+        assert!(ring_buffer.iter().eq(&[3, 4, 5]));
+        assert!(ring_buffer.iter().rev().eq(&[5, 4, 3]));
+        assert_eq!(ring_buffer.iter().len(), 3);
+
+        now.set(100);
+        assert_eq!(ring_buffer.iter().next(), None);
+        ring_buffer.write(6);
+        assert!(ring_buffer.into_inner().iter().eq(&[(100, 6)]));
+    }
+}
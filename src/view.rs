@@ -0,0 +1,135 @@
+//! A ring buffer over memory provided by the caller
+use core::mem::MaybeUninit;
+
+use crate::{Cursor, GenericRingBuffer};
+use crate::policy::Overwrite;
+
+/// A FIFO ring buffer that borrows its slots, such as a statically allocated or linker-placed
+/// region, instead of owning them
+///
+/// It has the same methods as [`RingBuffer`](crate::RingBuffer). Elements still in the view when
+/// it is dropped are dropped with it
+pub type RingBufferView<'a, T, P = Overwrite, C = usize> =
+    GenericRingBuffer<T, &'a mut [MaybeUninit<T>], P, C>;
+
+impl<'a, T, P, C: Cursor> RingBufferView<'a, T, P, C> {
+    /// Creates a new, empty RingBufferView instance over `slots`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `slots` is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::mem::MaybeUninit;
+    ///
+    /// let mut slots = [const { MaybeUninit::uninit() }; 3];
+    /// let mut ring_buffer: sringbuf::RingBufferView<u8> = sringbuf::RingBufferView::new(&mut slots);
+    /// ring_buffer.write(1);
+    /// assert_eq!(ring_buffer.read(), Some(1));
+    /// ```
+    pub fn new(slots: &'a mut [MaybeUninit<T>]) -> RingBufferView<'a, T, P, C> {
+        GenericRingBuffer::from_storage(slots)
+    }
+}
+
+/// A FIFO ring buffer that borrows a slice of initialized `Copy` values as its slots
+///
+/// Unlike a [`RingBufferView`], it can reuse ordinary memory such as a `[u8; N]` buffer, which
+/// stays initialized throughout: its storage is not an [`UninitStorage`](crate::UninitStorage),
+/// so slots are never handed out for filling in place, and
+/// [`into_inner`](GenericRingBuffer::into_inner) gives the borrowed slice back
+pub type RingBufferSlice<'a, T, P = Overwrite, C = usize> = GenericRingBuffer<T, &'a mut [T], P, C>;
+
+impl<'a, T: Copy, P, C: Cursor> RingBufferSlice<'a, T, P, C> {
+    /// Creates a new, empty RingBufferSlice instance over `slice`
+    ///
+    /// The previous contents of `slice` are ignored. Once the ring buffer is dropped, `slice`
+    /// holds whatever was last written to each of its elements
+    ///
+    /// # Panics
+    ///
+    /// Panics if `slice` is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut memory = [0u8; 3];
+    /// let mut ring_buffer: sringbuf::RingBufferSlice<u8> = sringbuf::RingBufferSlice::from_slice(&mut memory);
+    /// ring_buffer.write(1);
+    /// assert_eq!(ring_buffer.read(), Some(1));
+    /// ```
+    pub fn from_slice(slice: &'a mut [T]) -> RingBufferSlice<'a, T, P, C> {
+        GenericRingBuffer::from_storage(slice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::policy::Panic;
+    use crate::{RingBufferSlice, RingBufferView};
+
+    use core::mem::MaybeUninit;
+
+    #[test]
+    fn new_wrap_around() {
+        let mut slots = [const { MaybeUninit::uninit() }; 3];
+        let mut ring_buffer: RingBufferView<u8> = RingBufferView::new(&mut slots);
+
+        ring_buffer.extend(1..=4);
+        assert_eq!(ring_buffer.as_slices(), (&[2, 3][..], &[4][..]));
+        assert_eq!(ring_buffer.read(), Some(2));
+    }
+
+    #[test]
+    fn debug_names_view() {
+        let mut slots = [const { MaybeUninit::uninit() }; 2];
+        let mut ring_buffer: RingBufferView<u8> = RingBufferView::new(&mut slots);
+
+        ring_buffer.write(1);
+        assert_eq!(
+            format!("{ring_buffer:?}"),
+            "RingBufferView { contents: [1], len: 1, capacity: 2, read_index: 0, write_index: 1 }"
+        );
+    }
+
+    #[test]
+    fn from_slice_writes_through() {
+        let mut memory = [0u8; 4];
+        {
+            let mut ring_buffer: RingBufferSlice<u8> = RingBufferSlice::from_slice(&mut memory);
+            ring_buffer.extend(1..=5);
+            assert_eq!(
+                ring_buffer.iter().copied().collect::<Vec<_>>(),
+                [2, 3, 4, 5]
+            );
+            assert_eq!(ring_buffer.read(), Some(2));
+        }
+
+        assert_eq!(memory, [5, 2, 3, 4]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_slice_policy_panic() {
+        let mut memory = [0u8; 1];
+        let mut ring_buffer: RingBufferSlice<u8, Panic> = RingBufferSlice::from_slice(&mut memory);
+
+        ring_buffer.write(1);
+        ring_buffer.write(2);
+    }
+
+    #[test]
+    fn from_slice_into_inner_initialized() {
+        let mut memory = [7u8; 3];
+        let mut ring_buffer: RingBufferSlice<u8> = RingBufferSlice::from_slice(&mut memory);
+        ring_buffer.extend([1, 2, 3, 4]);
+        assert_eq!(ring_buffer.read(), Some(2));
+
+        let (slice, len) = ring_buffer.into_inner();
+        assert_eq!((&slice[..len], len), (&[3, 4][..], 2));
+        slice[2] = 9;
+        assert_eq!(memory, [3, 4, 9]);
+    }
+}
@@ -0,0 +1,158 @@
+//! A waker slot that one task registers in and any thread wakes through
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::task::Waker;
+
+/// No registration or wake-up is in progress
+const WAITING: usize = 0;
+/// A task is replacing the stored waker
+const REGISTERING: usize = 0b01;
+/// A thread is taking the stored waker to wake it
+const WAKING: usize = 0b10;
+
+/// Holds the waker of a task waiting for an event, such as data arriving in a queue
+///
+/// Registering and waking race through a small state machine instead of a lock: a wake-up that
+/// arrives while a waker is being registered is handed to the registering task, which wakes
+/// itself, so no wake-up is ever lost. Neither side allocates or blocks, so an interrupt
+/// handler can wake a task on a `no_std` executor, and the slot can live in a `static`
+///
+/// # Examples
+///
+/// ```
+/// use std::task::Waker;
+///
+/// static WAKER: sringbuf::AtomicWaker = sringbuf::AtomicWaker::new();
+///
+/// WAKER.register(Waker::noop());
+/// WAKER.wake();
+/// ```
+pub struct AtomicWaker {
+    state: AtomicUsize,
+    waker: UnsafeCell<Option<Waker>>,
+}
+
+// SAFETY: `waker` is only accessed by whoever moved `state` out of `WAITING`, which serializes
+// every access to it
+unsafe impl Send for AtomicWaker {}
+
+// SAFETY: see the `Send` impl
+unsafe impl Sync for AtomicWaker {}
+
+impl AtomicWaker {
+    /// Creates a new, empty AtomicWaker instance
+    pub const fn new() -> AtomicWaker {
+        AtomicWaker {
+            state: AtomicUsize::new(WAITING),
+            waker: UnsafeCell::new(None),
+        }
+    }
+
+    /// Stores `waker` to be woken by the next call to [`AtomicWaker::wake`], replacing any
+    /// waker stored before
+    ///
+    /// A task should register before its final check for the event it waits on. Only one task
+    /// is meant to register at a time; a registration racing another one is dropped
+    pub fn register(&self, waker: &Waker) {
+        match self.state.compare_exchange(
+            WAITING,
+            REGISTERING,
+            Ordering::Acquire,
+            Ordering::Acquire,
+        ) {
+            Ok(_) => {
+                // SAFETY: moving the state to `REGISTERING` grants exclusive access to `waker`
+                let previous = unsafe { &mut *self.waker.get() }.replace(waker.clone());
+
+                if let Err(_waking) = self.state.compare_exchange(
+                    REGISTERING,
+                    WAITING,
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                ) {
+                    // A wake-up arrived meanwhile and left the waker for this task to wake
+                    // SAFETY: the state is still `REGISTERING | WAKING`, so access is exclusive
+                    let waker = unsafe { &mut *self.waker.get() }.take();
+                    self.state.swap(WAITING, Ordering::AcqRel);
+                    if let Some(waker) = waker {
+                        waker.wake();
+                    }
+                }
+
+                drop(previous);
+            }
+            Err(WAKING) => waker.wake_by_ref(),
+            Err(_) => {}
+        }
+    }
+
+    /// Wakes the registered task, if any, and clears the slot
+    pub fn wake(&self) {
+        // Otherwise the registering task wakes itself, or another thread is already waking it
+        if self.state.fetch_or(WAKING, Ordering::AcqRel) == WAITING {
+            // SAFETY: setting `WAKING` from `WAITING` grants exclusive access to `waker`
+            let waker = unsafe { &mut *self.waker.get() }.take();
+            self.state.fetch_and(!WAKING, Ordering::Release);
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl Default for AtomicWaker {
+    /// Creates an empty slot
+    fn default() -> AtomicWaker {
+        AtomicWaker::new()
+    }
+}
+
+impl fmt::Debug for AtomicWaker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AtomicWaker").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::waker::AtomicWaker;
+
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::task::{Wake, Waker};
+
+    struct CountingWaker(AtomicUsize);
+
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<CountingWaker>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn wake_registered_once() {
+        let counter = Arc::new(CountingWaker(AtomicUsize::new(0)));
+        let atomic_waker = AtomicWaker::new();
+
+        atomic_waker.wake();
+        atomic_waker.register(&Waker::from(Arc::clone(&counter)));
+        atomic_waker.wake();
+        atomic_waker.wake();
+        assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn register_replaces_waker() {
+        let first = Arc::new(CountingWaker(AtomicUsize::new(0)));
+        let second = Arc::new(CountingWaker(AtomicUsize::new(0)));
+        let atomic_waker = AtomicWaker::new();
+
+        atomic_waker.register(&Waker::from(Arc::clone(&first)));
+        atomic_waker.register(&Waker::from(Arc::clone(&second)));
+        atomic_waker.wake();
+        assert_eq!(first.0.load(Ordering::SeqCst), 0);
+        assert_eq!(second.0.load(Ordering::SeqCst), 1);
+        assert_eq!(Arc::strong_count(&first), 1);
+    }
+}
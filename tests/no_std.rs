@@ -0,0 +1,27 @@
+//! Uses the ring buffers from a `no_std` crate, so the core API keeps building without `std`
+//!
+//! The tests only exist under `--no-default-features`: with the `std` feature, `sringbuf`
+//! links `std` itself. The CI also builds the crate for targets that have no `std` at all
+#![cfg(not(feature = "std"))]
+#![no_std]
+
+use sringbuf::RingBuffer;
+use sringbuf::policy::Reject;
+
+#[test]
+fn ring_buffer_without_std() {
+    let mut ring_buffer: RingBuffer<u8, 3> = RingBuffer::new();
+    ring_buffer.extend([1, 2, 3, 4]);
+    assert!(ring_buffer.iter().eq(&[2, 3, 4]));
+    assert_eq!(ring_buffer.read(), Some(2));
+}
+
+#[test]
+fn reject_without_std() {
+    let mut ring_buffer: RingBuffer<u8, 1, Reject> = RingBuffer::new();
+    assert_eq!(ring_buffer.write(1), None);
+    assert_eq!(
+        ring_buffer.try_write(2).map_err(|error| error.into_inner()),
+        Err(2)
+    );
+}